@@ -0,0 +1,44 @@
+/// Generates a large topographic map for benchmarking day 10, as puzzle inputs are not committed:
+///
+/// ```sh
+/// cargo run --release --example trail_map > data/2024/inputs/10.txt
+/// ```
+///
+/// The map is the same on every run. Its peaks are placed at random and every cell is 9 minus
+/// its Manhattan distance to the nearest peak, or 0 if that is negative, so trails overlap a lot.
+const SIZE: usize = 57;
+const PEAKS: usize = 40;
+const SEED: u64 = 10;
+
+/// xorshift64, good enough to scatter the peaks.
+struct Rng(u64);
+
+impl Rng {
+    fn below(&mut self, n: usize) -> usize {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 % n as u64) as usize
+    }
+}
+
+fn main() {
+    let mut rng = Rng(SEED);
+    let peaks: Vec<(usize, usize)> = (0..PEAKS)
+        .map(|_| (rng.below(SIZE), rng.below(SIZE)))
+        .collect();
+
+    for row in 0..SIZE {
+        let line: String = (0..SIZE)
+            .map(|col| {
+                let distance = peaks
+                    .iter()
+                    .map(|&(r, c)| r.abs_diff(row) + c.abs_diff(col))
+                    .min()
+                    .unwrap_or(usize::MAX);
+                char::from(b'0' + 9u8.saturating_sub(distance.min(9) as u8))
+            })
+            .collect();
+        println!("{line}");
+    }
+}
@@ -1,6 +1,8 @@
-advent_of_code::solution!(1);
+advent_of_code::solution!(2024, 1);
 
-pub fn part_one(input: &str) -> Option<u32> {
+use advent_of_code::parse;
+
+pub fn part_one(input: &str) -> parse::Result<u32> {
     // Read in one line at a time.
     // Each line has the form "n0 n1" where n0 and n1 are positive integers.
     // For each line, when it is read, parse it to n0 and n1.
@@ -9,26 +11,19 @@ pub fn part_one(input: &str) -> Option<u32> {
     // Then parallel-iterate over v0 and v1, and for each pair of elements n0 and n1, calculate the (absolute value) distance between n0 and n1.
     // Sum all the distances, and return the sum.
     // (Optionally, could do sum in parallel with iterator.)
-    let mut v0 = vec![];
-    let mut v1 = vec![];
-    for line in input.lines() {
-        let mut parts = line.split_whitespace();
-        let n0 = parts.next()?.parse::<u32>().ok()?;
-        let n1 = parts.next()?.parse::<u32>().ok()?;
-        v0.push(n0);
-        v1.push(n1);
-    }
+    let (mut v0, mut v1): (Vec<_>, Vec<_>) =
+        parse::pairs::<u32, u32>(input, " ")?.into_iter().unzip();
     v0.sort_unstable();
     v1.sort_unstable();
     let sum = v0
         .iter()
         .zip(v1.iter())
-        .map(|(n0, n1)| if n0 > n1 { n0 - n1 } else { n1 - n0 })
+        .map(|(n0, n1)| n0.abs_diff(*n1))
         .sum();
-    Some(sum)
+    Ok(sum)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
+pub fn part_two(input: &str) -> parse::Result<u32> {
     // Read in one line at a time.
     // Each line has the form "n0 n1" where n0 and n1 are positive integers.
     // For each line, when it is read, parse it to n0 and n1.
@@ -38,30 +33,17 @@ pub fn part_two(input: &str) -> Option<u32> {
     // Return the sum of all similarity scores using the iterator.
     let mut v = vec![];
     let mut counts = std::collections::HashMap::new();
-    for line in input.lines() {
-        let mut parts = line.split_whitespace();
-        let n0 = parts.next()?.parse::<u32>().ok()?;
-        let n1 = parts.next()?.parse::<u32>().ok()?;
+    for (n0, n1) in parse::pairs::<u32, u32>(input, " ")? {
         v.push(n0);
         *counts.entry(n1).or_insert(0) += 1;
     }
     let sum = v.iter().map(|n0| n0 * counts.get(n0).unwrap_or(&0)).sum();
-    Some(sum)
+    Ok(sum)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(11));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(31));
-    }
+    advent_of_code::example_tests!();
 }
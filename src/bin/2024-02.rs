@@ -1,4 +1,6 @@
-advent_of_code::solution!(2);
+advent_of_code::solution!(2024, 2);
+
+use advent_of_code::parse;
 
 // Check that level0 and level1 are actually increasing (or
 // decreasing), never equal.  Then check whether the increase (or
@@ -53,7 +55,7 @@ fn report_is_tolerably_safe(levels: &[u32]) -> bool {
     false
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
+pub fn part_one(input: &str) -> parse::Result<u32> {
     // Read one line at a time.
 
     // For each line, parse into whitespace-delimited integers
@@ -61,25 +63,14 @@ pub fn part_one(input: &str) -> Option<u32> {
 
     // For each report, determine whether it is safe.
     // Return number of safe reports by summing over iterator.
-    Some(
-        input
-            .lines()
-            .filter_map(|line| {
-                let levels: Vec<u32> = line
-                    .split_whitespace()
-                    .filter_map(|num| num.parse::<u32>().ok())
-                    .collect();
-                if report_is_safe(&levels) {
-                    Some(1)
-                } else {
-                    None
-                }
-            })
-            .sum(),
-    )
+    let reports = parse::lines(input, |line| line.integers::<u32>())?;
+    Ok(reports
+        .iter()
+        .filter(|levels| report_is_safe(levels))
+        .count() as u32)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
+pub fn part_two(input: &str) -> parse::Result<u32> {
     // Read one line at a time.
 
     // For each line, parse into whitespace-delimited integers
@@ -87,37 +78,16 @@ pub fn part_two(input: &str) -> Option<u32> {
 
     // For each report, determine whether it is tolerably safe.
     // Return number of safe reports by summing over iterator.
-    Some(
-        input
-            .lines()
-            .filter_map(|line| {
-                let levels: Vec<u32> = line
-                    .split_whitespace()
-                    .filter_map(|num| num.parse::<u32>().ok())
-                    .collect();
-                if report_is_tolerably_safe(&levels) {
-                    Some(1)
-                } else {
-                    None
-                }
-            })
-            .sum(),
-    )
+    let reports = parse::lines(input, |line| line.integers::<u32>())?;
+    Ok(reports
+        .iter()
+        .filter(|levels| report_is_tolerably_safe(levels))
+        .count() as u32)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(2));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(4));
-    }
+    advent_of_code::example_tests!();
 }
@@ -0,0 +1,75 @@
+advent_of_code::solution!(2024, 4);
+
+use advent_of_code::{grid::Grid, parse};
+use aho_corasick::AhoCorasick;
+
+fn parse_grid(input: &str) -> parse::Result<Grid<u8>> {
+    Grid::try_parse(input, |c| c.is_ascii().then_some(c as u8))
+}
+
+fn extract_all_sequences(grid: &Grid<u8>) -> Vec<Vec<u8>> {
+    // Rows, columns and diagonals in both directions.
+    let rows = grid.iter_rows().map(|row| row.to_vec());
+    let cols = grid.iter_cols().map(|col| col.copied().collect());
+    let diagonals = grid.diagonals().map(|diag| diag.copied().collect());
+    let anti_diagonals = grid.anti_diagonals().map(|diag| diag.copied().collect());
+
+    rows.chain(cols)
+        .chain(diagonals)
+        .chain(anti_diagonals)
+        .collect()
+}
+
+pub fn part_one(input: &str) -> parse::Result<u32> {
+    // Search forward and backward simultaneously.
+    let patterns = &[b"XMAS", b"SAMX"];
+    let ac = AhoCorasick::new(patterns).unwrap();
+
+    let sequences = extract_all_sequences(&parse_grid(input)?);
+    Ok(sequences
+        .iter()
+        .map(|haystack| ac.find_overlapping_iter(haystack).count() as u32)
+        .sum())
+}
+
+pub fn part_two(input: &str) -> parse::Result<u32> {
+    let grid = parse_grid(input)?;
+    let at = |row_idx, col_idx| grid[(row_idx, col_idx)];
+
+    // Find all X-MAS patterns in the 2d grid. Define X-MAS
+    // as a 3x3 grid with a pattern of two diagonals spelling out MAS
+    // in any direction, e.g.
+    //
+    // M.S
+    // .A.
+    // M.S
+    //
+    // where the . are any character.
+    let mut sum = 0;
+    for row_idx in 0..grid.rows().saturating_sub(2) {
+        for col_idx in 0..grid.cols().saturating_sub(2) {
+            // First check the middle of the grid for an A.
+            // Then check the top-left to bottom-right diagonal for two
+            // possible directions of the M and S. Then check
+            // the top-right to bottom-left diagonal for the other
+            // two possible directions of the M and S.
+            if at(row_idx + 1, col_idx + 1) == b'A'
+                && (at(row_idx, col_idx) == b'M' && at(row_idx + 2, col_idx + 2) == b'S'
+                    || at(row_idx + 2, col_idx + 2) == b'M' && at(row_idx, col_idx) == b'S')
+                && (at(row_idx, col_idx + 2) == b'M' && at(row_idx + 2, col_idx) == b'S'
+                    || at(row_idx + 2, col_idx) == b'M' && at(row_idx, col_idx + 2) == b'S')
+            {
+                sum += 1;
+            }
+        }
+    }
+
+    Ok(sum)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    advent_of_code::example_tests!();
+}
@@ -0,0 +1,107 @@
+advent_of_code::solution!(2024, 5);
+
+use std::collections::HashMap;
+
+// For creating a graph of constraints.
+use advent_of_code::graph;
+use advent_of_code::parse::{self, Span};
+
+type Page = u8;
+
+type Graph = graph::Graph<Page>;
+
+/// A list of pages with its line, for reporting errors.
+type Rule<'a> = (Span<'a>, Vec<Page>);
+
+// Lines of the form:
+//
+// 47|53
+// 97|13
+// 97|61
+fn parse_graph(section: Span) -> parse::Result<Graph> {
+    Ok(Graph::from_edges(parse::pairs::<Page, Page>(section, "|")?))
+}
+
+// The graph section, followed by a blank line and lines of the form:
+//
+// 75,47,61,53,29
+fn parse_input(input: &str) -> parse::Result<(Graph, Vec<Rule<'_>>)> {
+    let mut sections = parse::sections(input);
+    let (Some(graph_section), Some(rules_section)) = (sections.next(), sections.next()) else {
+        return Err(Span::from(input).error("expected two sections"));
+    };
+
+    let graph = parse_graph(graph_section)?;
+    let rules = parse::lines(rules_section, |line| Ok((line, line.integers::<Page>()?)))?;
+
+    Ok((graph, rules))
+}
+
+// Return whether the pages are in a valid order according
+// to the constraints encoded in the graph that involve only
+// the pages in the list.
+//
+// Just run through the whole graph.
+fn is_valid_order(graph: &Graph, pages: &[Page]) -> bool {
+    // Page -> index into pages.
+    let position: HashMap<Page, usize> = pages
+        .iter()
+        .enumerate()
+        .map(|(i, &page)| (page, i))
+        .collect();
+
+    // Ignore constraints if src or dest is not in pages, and
+    // look for a violation.
+    !graph.all_edges().any(|(src, dest, _)| {
+        matches!(
+            (position.get(&src), position.get(&dest)),
+            (Some(src_pos), Some(dest_pos)) if src_pos >= dest_pos
+        )
+    })
+}
+
+pub fn part_one(input: &str) -> parse::Result<u32> {
+    let (graph, rules) = parse_input(input)?;
+
+    // Return sum of the middle page of each valid rule.
+    Ok(rules
+        .iter()
+        .filter_map(|(_, pages)| {
+            if is_valid_order(&graph, pages) {
+                let middle = pages[pages.len() / 2];
+                Some(middle as u32)
+            } else {
+                None
+            }
+        })
+        .sum())
+}
+
+// Reorder pages to satisfy the graph constraints, ignoring again
+// all constraints that don't involve the pages in the list, by a
+// topological sort of the pages.
+fn fix_pages(graph: &Graph, pages: &[Page]) -> Result<Vec<Page>, graph::Cycle<Page>> {
+    graph::toposort_subset(graph, pages)
+}
+
+pub fn part_two(input: &str) -> parse::Result<u32> {
+    let (graph, rules) = parse_input(input)?;
+
+    // Return sum of the middle page of each fixed invalid rule.
+    rules
+        .iter()
+        .filter(|(_, pages)| !is_valid_order(&graph, pages))
+        .map(|(line, pages)| {
+            let fixed_pages = fix_pages(&graph, pages).map_err(|cycle| line.error(cycle))?;
+            let middle = fixed_pages[fixed_pages.len() / 2];
+            Ok(middle as u32)
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    advent_of_code::example_tests!();
+}
@@ -0,0 +1,154 @@
+advent_of_code::solution!(2024, 6, explain = explain);
+
+use std::fmt::Write;
+
+use advent_of_code::parse;
+use advent_of_code::patrol::{Lab, Loop, State};
+use advent_of_code::point::Direction;
+use advent_of_code::visualize::{self, Color, Frame};
+
+pub fn part_one(input: &str) -> parse::Result<u32> {
+    // Parse the input into a grid and the guard's position.
+    let lab = Lab::parse(input)?;
+
+    // Walk the guard, returning all location/orientation pairs.
+    let patrol = lab.patrol();
+
+    if visualize::is_active() {
+        for step in 1..=patrol.states.len() {
+            visualize::show(|| {
+                frame(&lab, &patrol.states[..step]).caption(format!("{step} states visited"))
+            });
+        }
+    }
+
+    if let Some(cycle) = patrol.cycle() {
+        return Err(never_leaves(&lab, &cycle));
+    }
+
+    // Get the visited locations.
+    Ok(patrol.positions().len() as u32)
+}
+
+/// Draw the grid with the visited locations and the guard at the
+/// last state.
+fn frame(lab: &Lab, states: &[State]) -> Frame {
+    let frame = Frame::new(
+        lab.obstacles(),
+        |&obstacle| if obstacle { '#' } else { '.' },
+    )
+    .highlight_all(states.iter().map(|&(pos, _)| pos), Color::Yellow);
+
+    match states.last() {
+        Some(&(pos, dir)) => frame.mark(pos, guard(dir), Color::Red),
+        None => frame,
+    }
+}
+
+/// The error for a guard that walks in a loop without placing an
+/// obstacle, pointing at the guard's start.
+fn never_leaves(lab: &Lab, cycle: &Loop) -> parse::Error {
+    let (row, col) = lab.start();
+    parse::Error {
+        line: row + 1,
+        column: col + 1,
+        message: format!("the guard never leaves the grid: {cycle}"),
+    }
+}
+
+fn guard(dir: Direction) -> char {
+    match dir {
+        Direction::Up => '^',
+        Direction::Right => '>',
+        Direction::Down => 'v',
+        Direction::Left => '<',
+    }
+}
+
+pub fn part_two(input: &str) -> parse::Result<u32> {
+    // Parse the input into a grid and the guard's position.
+    let lab = Lab::parse(input)?;
+
+    // Find the positions along the visited path, except for the
+    // initial one, where placing an obstacle results in an infinite
+    // loop.
+    let obstacles = lab
+        .loop_obstacles()
+        .map_err(|cycle| never_leaves(&lab, &cycle))?;
+
+    // Show the loop caused by each new obstacle.
+    for (i, &pos) in obstacles.iter().enumerate() {
+        visualize::show(|| {
+            let obstruction = lab.obstruction(pos).expect("the obstacle causes a loop");
+            frame(&lab, &obstruction.cycle.states)
+                .mark(pos, 'O', Color::Magenta)
+                .caption(format!("loop {}", i + 1))
+        });
+    }
+
+    Ok(obstacles.len() as u32)
+}
+
+/// The grid with the guard's path and every obstacle that makes the
+/// guard loop, followed by the loop of each obstacle.
+pub fn explain(input: &str) -> parse::Result<String> {
+    let lab = Lab::parse(input)?;
+    let patrol = lab.patrol();
+    let positions = patrol.positions();
+
+    let mut chars = lab
+        .obstacles()
+        .map(|&obstacle| if obstacle { '#' } else { '.' });
+    for &pos in &positions {
+        chars[pos] = 'X';
+    }
+
+    let mut out = String::new();
+    let obstructions = match lab.obstructions() {
+        Ok(obstructions) => {
+            let &((row, col), dir) = patrol.states.last().expect("the guard has a state");
+            writeln!(
+                out,
+                "The guard visits {} positions and leaves the grid at ({row}, {col}) facing {dir:?}.",
+                positions.len()
+            )
+            .unwrap();
+            obstructions
+        }
+        Err(cycle) => {
+            writeln!(out, "The guard never leaves the grid: {cycle}.").unwrap();
+            vec![]
+        }
+    };
+
+    for obstruction in &obstructions {
+        chars[obstruction.pos] = 'O';
+    }
+    chars[lab.start()] = '^';
+
+    writeln!(out, "\n{chars}").unwrap();
+    writeln!(
+        out,
+        "^ start, X path, # obstacle, O new obstacle that makes the guard loop\n"
+    )
+    .unwrap();
+
+    writeln!(
+        out,
+        "{} new obstacles make the guard loop, positions are (row, column):",
+        obstructions.len()
+    )
+    .unwrap();
+    for (i, obstruction) in obstructions.iter().enumerate() {
+        writeln!(out, "{:>4}. {obstruction}", i + 1).unwrap();
+    }
+
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    advent_of_code::example_tests!();
+}
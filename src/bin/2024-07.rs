@@ -0,0 +1,77 @@
+advent_of_code::solution!(2024, 7, explain = explain);
+
+use std::fmt::Write;
+
+use advent_of_code::equation::{Equation, Op};
+use advent_of_code::parse;
+
+/// The operators of part one.
+const OPERATORS: [Op; 2] = [Op::Add, Op::Mul];
+
+/// The operators of part two, adding || which is concatenation of
+/// digits as strings.
+const OPERATORS_2: [Op; 3] = [Op::Add, Op::Mul, Op::Concat];
+
+/// "45: 14 4 8"
+fn parse_input(input: &str) -> parse::Result<Vec<Equation>> {
+    Ok(parse::records(input, ":")?
+        .into_iter()
+        .map(|(target, xs)| Equation::new(target, xs))
+        .collect())
+}
+
+/// Return the sum of all targets that can be formed from the xs
+/// using left-to-right evaluation with `operators`.
+fn total_calibration(input: &str, operators: &[Op]) -> parse::Result<i64> {
+    let equations = parse_input(input)?;
+    Ok(equations
+        .iter()
+        .filter(|equation| equation.is_solvable(operators))
+        .map(|equation| equation.target)
+        .sum())
+}
+
+pub fn part_one(input: &str) -> parse::Result<i64> {
+    total_calibration(input, &OPERATORS)
+}
+
+pub fn part_two(input: &str) -> parse::Result<i64> {
+    total_calibration(input, &OPERATORS_2)
+}
+
+/// Every equation with the expressions that make it true, using the
+/// operators of part two.
+pub fn explain(input: &str) -> parse::Result<String> {
+    let equations = parse_input(input)?;
+
+    let mut out = String::new();
+    for equation in &equations {
+        let solutions = equation.solutions(&OPERATORS_2);
+        // Part one if some solution does without concatenation.
+        let part = if solutions
+            .iter()
+            .any(|e| e.operators.iter().all(|&&op| op != Op::Concat))
+        {
+            "part one"
+        } else {
+            "part two"
+        };
+        match solutions.len() {
+            0 => writeln!(out, "{}: no solution", equation.target),
+            n => writeln!(out, "{}: {part}, {n} solution(s)", equation.target),
+        }
+        .unwrap();
+        for expression in solutions {
+            writeln!(out, "    {expression}").unwrap();
+        }
+    }
+
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    advent_of_code::example_tests!();
+}
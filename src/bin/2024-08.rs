@@ -0,0 +1,162 @@
+advent_of_code::solution!(2024, 8);
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use advent_of_code::grid::{self, Grid};
+use advent_of_code::parse;
+use advent_of_code::point::Point;
+use advent_of_code::visualize::{self, Color, Frame};
+
+type Location = grid::Pos;
+
+struct Antennas {
+    grid: Grid<u8>,
+    marks: HashMap<u8, Vec<Location>>,
+}
+
+/// A valid antipode within the grid, formed using any two
+/// non-dots in the grid with the same mark, by going to either
+/// side along a line, using the same distance away from each
+/// pair of non-dots, in the opposite directions, while remaining
+/// on the bytes grid.
+struct Antipodes {
+    antipodes: HashMap<u8, Vec<Location>>,
+}
+
+impl Antipodes {
+    /// For each mark type, find all antipodes of the type.
+    fn new(antennas: &Antennas) -> Self {
+        let mut antipodes = HashMap::new();
+
+        for (mark, locations) in &antennas.marks {
+            let mut antipode_list = vec![];
+
+            // For each pair of locations that is a non-dot.
+            for i in 0..locations.len() {
+                for j in (i + 1)..locations.len() {
+                    // Call the two points point 1 and 2.
+                    // We want to look for possible point 0 and 3.
+                    let delta = Point::from(locations[j]) - Point::from(locations[i]);
+
+                    // Try to get antipodes 0 and 3, checking that they
+                    // are within the grid.
+                    // Push without worrying about duplicates for now.
+                    let grid = &antennas.grid;
+                    antipode_list.extend(grid.offset(locations[i], -delta));
+                    antipode_list.extend(grid.offset(locations[j], delta));
+                }
+            }
+
+            antipodes.insert(*mark, antipode_list);
+        }
+
+        Antipodes { antipodes }
+    }
+
+    /// Just like new, except that we create a lot more antipodes:
+    /// All grid locations evenly spaced on the line extending beyond
+    /// both ends of any two original marks, including those original
+    /// marks, is an antipode. So we can't just look for two anymore,
+    /// but must loop in both directions till off the grid.
+    fn new_on_line(antennas: &Antennas) -> Self {
+        let mut antipodes = HashMap::new();
+
+        for (mark, locations) in &antennas.marks {
+            let mut antipode_list = vec![];
+
+            for i in 0..locations.len() {
+                for j in (i + 1)..locations.len() {
+                    let delta = Point::from(locations[j]) - Point::from(locations[i]);
+
+                    // Push all antipodes on the line, starting from
+                    // the original marks, till off the grid.
+                    let grid = &antennas.grid;
+                    let mut pos = Some(locations[i]);
+                    while let Some(p) = pos {
+                        antipode_list.push(p);
+                        pos = grid.offset(p, -delta);
+                    }
+
+                    let mut pos = Some(locations[j]);
+                    while let Some(p) = pos {
+                        antipode_list.push(p);
+                        pos = grid.offset(p, delta);
+                    }
+                }
+            }
+
+            antipodes.insert(*mark, antipode_list);
+        }
+
+        Antipodes { antipodes }
+    }
+
+    /// Show the antipodes of each mark type when visualizing.
+    fn visualize(&self, antennas: &Antennas) {
+        if !visualize::is_active() {
+            return;
+        }
+
+        let mut marks: Vec<_> = self.antipodes.keys().collect();
+        marks.sort();
+
+        for mark in marks {
+            visualize::show(|| {
+                Frame::new(&antennas.grid, |&byte| byte as char)
+                    .highlight_all(antennas.marks[mark].iter().copied(), Color::Green)
+                    .highlight_all(self.antipodes[mark].iter().copied(), Color::Red)
+                    .caption(format!("antipodes of {}", *mark as char))
+            });
+        }
+    }
+
+    fn unique_locations(self) -> HashSet<Location> {
+        self.antipodes.into_values().flatten().collect()
+    }
+}
+
+impl Antennas {
+    /// Read in a row of lines, each ASCII character of which is either
+    /// . (a dot) or a digit or lower ASCII letter or upper ASCII
+    /// letter. If the character is not a dot, store its grid location in
+    /// a map. Return the grid (using bytes) as well as the map of all
+    /// non-dot locations keyed by the non-dot.
+    fn new(input: &str) -> parse::Result<Self> {
+        let grid = Grid::try_parse(input, |c| c.is_ascii().then_some(c as u8))?;
+        let mut marks = HashMap::new();
+
+        for (pos, &byte) in grid.iter() {
+            if byte != b'.' {
+                marks.entry(byte).or_insert_with(Vec::new).push(pos);
+            }
+        }
+
+        Ok(Antennas { grid, marks })
+    }
+}
+
+pub fn part_one(input: &str) -> parse::Result<u32> {
+    let antennas = Antennas::new(input)?;
+    let antipodes = Antipodes::new(&antennas);
+    antipodes.visualize(&antennas);
+    let unique_locations = antipodes.unique_locations();
+
+    Ok(unique_locations.len() as u32)
+}
+
+pub fn part_two(input: &str) -> parse::Result<u32> {
+    let antennas = Antennas::new(input)?;
+    let antipodes = Antipodes::new_on_line(&antennas);
+    antipodes.visualize(&antennas);
+    let unique_locations = antipodes.unique_locations();
+
+    Ok(unique_locations.len() as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    advent_of_code::example_tests!();
+}
@@ -1,9 +1,25 @@
-advent_of_code::solution!(9);
-
-const DEBUG: bool = false;
+advent_of_code::solution!(2024, 9);
 
 use std::collections::BTreeMap;
 
+use advent_of_code::visualize::{self, Color, Frame};
+
+/// Number of blocks per row when visualizing.
+const FRAME_WIDTH: usize = 100;
+
+/// Draw a block, using only the last digit of IDs.
+fn block_char(block: &Block) -> char {
+    match block {
+        Block::Id(id) => char::from(b'0' + (id % 10) as u8),
+        Block::Free => '.',
+    }
+}
+
+/// Position of a block in a frame.
+fn frame_pos(index: usize) -> (usize, usize) {
+    (index / FRAME_WIDTH, index % FRAME_WIDTH)
+}
+
 #[derive(Debug)]
 enum Block {
     /// Numbered starting from 0, left to right when parsing input.
@@ -62,16 +78,8 @@ impl Disk {
         Self { blocks }
     }
 
-    /// Suitable for single-digit IDs only.
-    fn small_id_debug(&self) -> String {
-        self.blocks
-            .iter()
-            .map(|block| match block {
-                Block::Id(id) => id.to_string(),
-                Block::Free => ".".to_string(),
-            })
-            .collect::<Vec<_>>()
-            .join("")
+    fn frame(&self) -> Frame {
+        Frame::wrap(self.blocks.iter().map(block_char), FRAME_WIDTH)
     }
 
     /// Compact the disk by making one step at a time, each
@@ -85,9 +93,7 @@ impl Disk {
     /// - If a pair of (left free, right non-free) is found, swap
     ///   them on the disk, then continue the process.
     fn compact(&mut self) {
-        if DEBUG {
-            eprintln!("{}", self.small_id_debug());
-        }
+        visualize::show(|| self.frame());
 
         let mut i0 = 0;
         let mut i1 = self.blocks.len();
@@ -110,9 +116,11 @@ impl Disk {
 
             // Swap the blocks.
             self.blocks.swap(i0, i1 - 1);
-            if DEBUG {
-                eprintln!("{}", self.small_id_debug());
-            }
+            visualize::show(|| {
+                self.frame()
+                    .highlight(frame_pos(i0), Color::Green)
+                    .highlight(frame_pos(i1 - 1), Color::Red)
+            });
         }
     }
 
@@ -193,21 +201,28 @@ impl Disk2 {
         }
     }
 
-    /// Suitable for single-digit IDs only.
-    fn small_id_debug(&self) -> String {
-        // Allocate byte vector of disk size.
-        let mut bytes = vec![b'.'; self.size as usize];
+    /// Draw the disk, highlighting the blocks of file `moved`, if any.
+    fn frame(&self, moved: Option<u32>) -> Frame {
+        // Allocate block vector of disk size.
+        let mut blocks: Vec<Block> = (0..self.size).map(|_| Block::Free).collect();
 
         for (id, file) in self.files.iter().enumerate() {
-            // Convert ASCII small id to digit as byte.
-            let id_byte = b'0' + id as u8;
-
             for i in 0..file.size {
                 let index = file.start + i as u32;
-                bytes[index as usize] = id_byte;
+                blocks[index as usize] = Block::Id(id as u16);
             }
         }
-        String::from_utf8(bytes).unwrap()
+
+        let frame = Frame::wrap(blocks.iter().map(block_char), FRAME_WIDTH);
+        let Some(file_id) = moved else {
+            return frame;
+        };
+
+        let file = &self.files[file_id as usize];
+        let indices = file.start..file.start + file.size as u32;
+        frame
+            .highlight_all(indices.map(|i| frame_pos(i as usize)), Color::Green)
+            .caption(format!("moved file {file_id} to {}", file.start))
     }
 
     fn move_file_to_leftmost_free_space(&mut self, file_id: u32) -> bool {
@@ -233,14 +248,6 @@ impl Disk2 {
 
                 new_start = free_start;
                 free_size = free_space.size;
-
-                if DEBUG {
-                    eprintln!(
-                        "Found space at {} of size {} for file {} of size {}",
-                        new_start, free_size, file_id, file_size
-                    );
-                }
-
                 break;
             }
         }
@@ -275,13 +282,11 @@ impl Disk2 {
     /// moves are done. We know where all of the ID files are,
     /// from initial compaction.
     fn compact(&mut self) {
-        if DEBUG {
-            eprintln!("{}", self.small_id_debug());
-        }
+        visualize::show(|| self.frame(None));
 
         for file_id in (0..self.files.len()).rev() {
-            if self.move_file_to_leftmost_free_space(file_id as u32) && DEBUG {
-                eprintln!("{}", self.small_id_debug());
+            if self.move_file_to_leftmost_free_space(file_id as u32) {
+                visualize::show(|| self.frame(Some(file_id as u32)));
             }
         }
     }
@@ -322,15 +327,5 @@ pub fn part_two(input: &str) -> Option<u64> {
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(1928));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(2858));
-    }
+    advent_of_code::example_tests!();
 }
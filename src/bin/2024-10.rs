@@ -0,0 +1,86 @@
+advent_of_code::solution!(2024, 10);
+
+use advent_of_code::grid::{self, Grid};
+use advent_of_code::parse;
+
+type Location = grid::Pos;
+
+const SUMMIT: u8 = 9;
+
+/// Read in lines that create a grid of digits '0' to '9'.
+fn parse_heights(input: &str) -> parse::Result<Grid<u8>> {
+    Grid::try_parse(input, |c| c.to_digit(10).map(|d| d as u8))
+}
+
+/// Fill in a value for every cell of the map from the summits down.
+/// A summit gets `summit(location)`. A trail from any other cell
+/// continues at one of its (maximum four) neighbors that are one
+/// higher, so its value combines the values of these neighbors,
+/// which have already been computed, starting from the default.
+fn from_summits<T: Default>(
+    heights: &Grid<u8>,
+    summit: impl Fn(Location) -> T,
+    mut combine: impl FnMut(&mut T, &T),
+) -> Grid<T> {
+    let mut layers = vec![Vec::new(); SUMMIT as usize + 1];
+    for (location, &height) in heights.iter() {
+        if let Some(layer) = layers.get_mut(height as usize) {
+            layer.push(location);
+        }
+    }
+
+    let mut values = heights.map(|_| T::default());
+    for (height, layer) in layers.iter().enumerate().rev() {
+        for &location in layer {
+            if height == SUMMIT as usize {
+                values[location] = summit(location);
+                continue;
+            }
+
+            let mut value = T::default();
+            for next in heights.neighbors4(location) {
+                if heights[next] as usize == height + 1 {
+                    combine(&mut value, &values[next]);
+                }
+            }
+            values[location] = value;
+        }
+    }
+
+    values
+}
+
+/// Count the distinct summits reachable from each trailhead.
+pub fn part_one(input: &str) -> parse::Result<u32> {
+    let heights = parse_heights(input)?;
+
+    // Sorted and without duplicates.
+    let summits = from_summits(
+        &heights,
+        |location| vec![location],
+        |reachable, next| {
+            reachable.extend_from_slice(next);
+            reachable.sort_unstable();
+            reachable.dedup();
+        },
+    );
+
+    Ok(heights
+        .find_all(&0)
+        .map(|head| summits[head].len() as u32)
+        .sum())
+}
+
+/// Count the distinct trails from each trailhead to any summit.
+pub fn part_two(input: &str) -> parse::Result<u64> {
+    let heights = parse_heights(input)?;
+    let paths = from_summits(&heights, |_| 1, |count, next| *count += next);
+    Ok(heights.find_all(&0).map(|head| paths[head]).sum())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    advent_of_code::example_tests!();
+}
@@ -0,0 +1,439 @@
+//! Equations like `3267: 81 40 27` whose operators are missing, as in 2024 day 7.
+//!
+//! Operators are evaluated left to right, ignoring the usual precedence rules. The operators to
+//! try are passed to every method, so puzzles can pick from the built-in [`Op`]s or bring their
+//! own by implementing [`Operator`].
+//!
+//! ```
+//! use advent_of_code::equation::{Equation, Op};
+//!
+//! let equation = Equation::new(3267, vec![81, 40, 27]);
+//! let operators = [Op::Add, Op::Mul];
+//! assert!(equation.is_solvable(&operators));
+//! assert_eq!(equation.count_solutions(&operators), 2);
+//! assert_eq!(equation.solve(&operators).unwrap().to_string(), "81 * 40 + 27");
+//! ```
+
+use std::{fmt::Display, ops::ControlFlow};
+
+/// The left operands for which an operator gives a result, see [`Operator::inverse`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Inverse {
+    None,
+    One(i64),
+    Two(i64, i64),
+    /// Every left operand gives the result, e.g. `x * 0 == 0`.
+    Any,
+}
+
+/// A binary operator on integers.
+pub trait Operator {
+    /// How the operator is written in an expression, e.g. `+`.
+    fn symbol(&self) -> &str;
+
+    /// `left op right`, or `None` if it is not defined or overflows.
+    fn apply(&self, left: i64, right: i64) -> Option<i64>;
+
+    /// The left operands for which `left op right == result`. The solver works backwards from
+    /// the target with this.
+    fn inverse(&self, result: i64, right: i64) -> Inverse;
+
+    /// Whether `left op right >= left` for positive operands. If all operators grow, the solver
+    /// discards intermediate values below 1 for equations with positive operands.
+    fn grows(&self) -> bool {
+        false
+    }
+}
+
+/// The built-in operators.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Op {
+    /// `+`
+    Add,
+    /// `*`
+    Mul,
+    /// `||`, appends the digits of the right operand to the left one, e.g. `12 || 345 == 12345`.
+    /// Only defined for operands that are not negative.
+    Concat,
+    /// `-`
+    Sub,
+    /// `^`, only defined for exponents that are not negative.
+    Pow,
+}
+
+impl Operator for Op {
+    fn symbol(&self) -> &str {
+        match self {
+            Op::Add => "+",
+            Op::Mul => "*",
+            Op::Concat => "||",
+            Op::Sub => "-",
+            Op::Pow => "^",
+        }
+    }
+
+    fn apply(&self, left: i64, right: i64) -> Option<i64> {
+        match self {
+            Op::Add => left.checked_add(right),
+            Op::Mul => left.checked_mul(right),
+            Op::Concat if left < 0 || right < 0 => None,
+            Op::Concat => left.checked_mul(shift(right)?)?.checked_add(right),
+            Op::Sub => left.checked_sub(right),
+            Op::Pow => left.checked_pow(u32::try_from(right).ok()?),
+        }
+    }
+
+    fn inverse(&self, result: i64, right: i64) -> Inverse {
+        let one = |left: Option<i64>| left.map_or(Inverse::None, Inverse::One);
+        match self {
+            Op::Add => one(result.checked_sub(right)),
+            Op::Sub => one(result.checked_add(right)),
+            Op::Mul if right == 0 => match result {
+                0 => Inverse::Any,
+                _ => Inverse::None,
+            },
+            Op::Mul => one(result
+                .checked_rem(right)
+                .filter(|&rem| rem == 0)
+                .and_then(|_| result.checked_div(right))),
+            Op::Concat if result < 0 || right < 0 => Inverse::None,
+            // Check whether the digits of `right` are the final digits of `result`.
+            Op::Concat => {
+                one(shift(right)
+                    .and_then(|shift| (result % shift == right).then_some(result / shift)))
+            }
+            Op::Pow if right < 0 => Inverse::None,
+            Op::Pow if right == 0 => match result {
+                1 => Inverse::Any,
+                _ => Inverse::None,
+            },
+            Op::Pow => root(result, right),
+        }
+    }
+
+    fn grows(&self) -> bool {
+        !matches!(self, Op::Sub)
+    }
+}
+
+/// The power of ten that makes room for the digits of `x`.
+fn shift(x: i64) -> Option<i64> {
+    10i64.checked_pow(x.checked_ilog10().unwrap_or(0) + 1)
+}
+
+/// The integers whose `n`th power is `x`, for `n >= 1`.
+fn root(x: i64, n: i64) -> Inverse {
+    if n == 1 {
+        return Inverse::One(x);
+    }
+    if x < 0 && n % 2 == 0 {
+        return Inverse::None;
+    }
+
+    // Only 0 and 1 have roots for huge exponents, and their roots are the same as for 64.
+    let exponent = u32::try_from(n.min(64)).unwrap_or(64);
+    let magnitude = x.unsigned_abs();
+    let guess = (magnitude as f64).powf(1.0 / exponent as f64).round() as u64;
+    let Some(root) =
+        (guess.saturating_sub(1)..=guess + 1).find(|r| r.checked_pow(exponent) == Some(magnitude))
+    else {
+        return Inverse::None;
+    };
+
+    // At most the square root of 2^63.
+    let root = root as i64;
+    match (root, x < 0, n % 2 == 0) {
+        (0, _, _) => Inverse::One(0),
+        (_, true, _) => Inverse::One(-root),
+        (_, false, true) => Inverse::Two(root, -root),
+        (_, false, false) => Inverse::One(root),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A target and the operands to combine into it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Equation {
+    pub target: i64,
+    pub operands: Vec<i64>,
+}
+
+/// Operands combined with operators, e.g. `81 + 40 * 27`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Expression<'a, O> {
+    pub operands: &'a [i64],
+    /// One less than the operands, `operators[i]` comes after `operands[i]`.
+    pub operators: Vec<&'a O>,
+}
+
+impl<O: Operator> Expression<'_, O> {
+    /// The value of the expression, evaluated left to right.
+    pub fn evaluate(&self) -> Option<i64> {
+        let (&first, rest) = self.operands.split_first()?;
+        rest.iter()
+            .zip(&self.operators)
+            .try_fold(first, |value, (&x, op)| op.apply(value, x))
+    }
+}
+
+impl<O: Operator> Display for Expression<'_, O> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, x) in self.operands.iter().enumerate() {
+            if i > 0 {
+                write!(f, " {} ", self.operators[i - 1].symbol())?;
+            }
+            write!(f, "{x}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Equation {
+    pub fn new(target: i64, operands: Vec<i64>) -> Self {
+        Self { target, operands }
+    }
+
+    /// Whether some choice of `operators` makes the operands equal the target.
+    pub fn is_solvable<O: Operator>(&self, operators: &[O]) -> bool {
+        self.solve(operators).is_some()
+    }
+
+    /// The first expression equal to the target, trying `operators` in order from the last
+    /// operand backwards.
+    pub fn solve<'a, O: Operator>(&'a self, operators: &'a [O]) -> Option<Expression<'a, O>> {
+        let mut found = None;
+        self.search(operators, |expression| {
+            found = Some(expression);
+            ControlFlow::Break(())
+        });
+        found
+    }
+
+    /// All expressions equal to the target.
+    pub fn solutions<'a, O: Operator>(&'a self, operators: &'a [O]) -> Vec<Expression<'a, O>> {
+        let mut found = Vec::new();
+        self.search(operators, |expression| {
+            found.push(expression);
+            ControlFlow::Continue(())
+        });
+        found
+    }
+
+    /// The number of choices of `operators` that make the operands equal the target.
+    pub fn count_solutions<O: Operator>(&self, operators: &[O]) -> u64 {
+        let mut count = 0;
+        self.search(operators, |_| {
+            count += 1;
+            ControlFlow::Continue(())
+        });
+        count
+    }
+
+    /// Call `visit` with every expression equal to the target, until it breaks.
+    fn search<'a, O: Operator>(
+        &'a self,
+        operators: &'a [O],
+        mut visit: impl FnMut(Expression<'a, O>) -> ControlFlow<()>,
+    ) {
+        if self.operands.is_empty() || operators.is_empty() {
+            return;
+        }
+
+        let search = Search {
+            operands: &self.operands,
+            operators,
+            positive: self.operands.iter().all(|&x| x > 0) && operators.iter().all(O::grows),
+        };
+        let mut chosen = vec![0; self.operands.len() - 1];
+        let _ = search.backwards(
+            self.target,
+            self.operands.len(),
+            &mut chosen,
+            &mut |chosen| {
+                visit(Expression {
+                    operands: &self.operands,
+                    operators: chosen.iter().map(|&i| &operators[i]).collect(),
+                })
+            },
+        );
+    }
+}
+
+/// The state of [`Equation::search`]. Operators are chosen by index.
+struct Search<'a, O> {
+    operands: &'a [i64],
+    operators: &'a [O],
+    /// Whether all intermediate values are positive.
+    positive: bool,
+}
+
+impl<O: Operator> Search<'_, O> {
+    /// Choose the operators between the first `len` operands so that they equal `target`,
+    /// starting with the last one.
+    fn backwards(
+        &self,
+        target: i64,
+        len: usize,
+        chosen: &mut [usize],
+        visit: &mut impl FnMut(&[usize]) -> ControlFlow<()>,
+    ) -> ControlFlow<()> {
+        if self.positive && target < 1 {
+            return ControlFlow::Continue(());
+        }
+
+        // If there is only one number remaining, it must be the target.
+        if len == 1 {
+            return match self.operands[0] == target {
+                true => visit(chosen),
+                false => ControlFlow::Continue(()),
+            };
+        }
+
+        let last = self.operands[len - 1];
+        for (i, op) in self.operators.iter().enumerate() {
+            chosen[len - 2] = i;
+            match op.inverse(target, last) {
+                Inverse::None => {}
+                Inverse::One(left) => self.backwards(left, len - 1, chosen, visit)?,
+                Inverse::Two(a, b) => {
+                    self.backwards(a, len - 1, chosen, visit)?;
+                    self.backwards(b, len - 1, chosen, visit)?;
+                }
+                Inverse::Any => self.forwards(self.operands[0], 1, len - 1, chosen, visit)?,
+            }
+        }
+        ControlFlow::Continue(())
+    }
+
+    /// Choose the operators between the first `len` operands in every way that can be
+    /// evaluated, with `value` being the value of the first `index` operands.
+    fn forwards(
+        &self,
+        value: i64,
+        index: usize,
+        len: usize,
+        chosen: &mut [usize],
+        visit: &mut impl FnMut(&[usize]) -> ControlFlow<()>,
+    ) -> ControlFlow<()> {
+        if index == len {
+            return visit(chosen);
+        }
+
+        for (i, op) in self.operators.iter().enumerate() {
+            if let Some(value) = op.apply(value, self.operands[index]) {
+                chosen[index - 1] = i;
+                self.forwards(value, index + 1, len, chosen, visit)?;
+            }
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL: [Op; 5] = [Op::Add, Op::Mul, Op::Concat, Op::Sub, Op::Pow];
+
+    #[test]
+    fn inverts_operators() {
+        let extremes = [i64::MIN, i64::MIN + 1, i64::MAX - 1, i64::MAX];
+        let lefts = (-20..=20).chain(extremes);
+        let rights: Vec<i64> = (-4..=4).chain([63, 64]).chain(extremes).collect();
+
+        for op in ALL {
+            for left in lefts.clone() {
+                for &right in &rights {
+                    // Must not overflow, whatever the result.
+                    op.inverse(left, right);
+
+                    let Some(result) = op.apply(left, right) else {
+                        continue;
+                    };
+                    let found = match op.inverse(result, right) {
+                        Inverse::None => false,
+                        Inverse::One(a) => a == left,
+                        Inverse::Two(a, b) => a == left || b == left,
+                        Inverse::Any => true,
+                    };
+                    assert!(found, "{left} {} {right} = {result}", op.symbol());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn reconstructs_expressions() {
+        let equation = Equation::new(156, vec![15, 6]);
+        assert!(!equation.is_solvable(&[Op::Add, Op::Mul]));
+        let concat = equation.solve(&[Op::Add, Op::Mul, Op::Concat]).unwrap();
+        assert_eq!(concat.to_string(), "15 || 6");
+        assert_eq!(concat.evaluate(), Some(156));
+
+        let equation = Equation::new(292, vec![11, 6, 16, 20]);
+        let solutions: Vec<String> = equation
+            .solutions(&[Op::Add, Op::Mul])
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(solutions, ["11 + 6 * 16 + 20"]);
+    }
+
+    #[test]
+    fn counts_solutions() {
+        // 2 + 2, 2 * 2, 2 ^ 2
+        let equation = Equation::new(4, vec![2, 2]);
+        assert_eq!(equation.count_solutions(&ALL), 3);
+
+        // Anything times 0, with every operator between the first two operands.
+        let equation = Equation::new(0, vec![3, 4, 0]);
+        assert_eq!(equation.count_solutions(&[Op::Add, Op::Mul]), 2);
+        assert_eq!(equation.count_solutions(&ALL), 5);
+
+        // Dividing the smallest integer by -1 overflows.
+        let equation = Equation::new(i64::MIN, vec![3, -1]);
+        assert_eq!(equation.count_solutions(&ALL), 0);
+        let equation = Equation::new(i64::MIN, vec![-2, 63]);
+        assert_eq!(equation.solve(&ALL).unwrap().to_string(), "-2 ^ 63");
+
+        // Negative intermediate values.
+        let equation = Equation::new(-8, vec![2, 4, 3]);
+        assert_eq!(equation.solve(&ALL).unwrap().to_string(), "2 - 4 ^ 3");
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        let equations = [
+            (7, vec![1, 2, 3, 4]),
+            (81, vec![3, 2, 2]),
+            (-1, vec![0, 1, 2]),
+        ];
+        for (target, operands) in equations {
+            let equation = Equation::new(target, operands);
+            let expected = equation
+                .solutions(&ALL)
+                .iter()
+                .filter(|e| e.evaluate() == Some(target))
+                .count();
+
+            // Every operator sequence, counted in base 5.
+            let slots = equation.operands.len() as u32 - 1;
+            let brute_force = (0..5usize.pow(slots))
+                .filter(|&n| {
+                    let operators = (0..slots).map(|i| &ALL[n / 5usize.pow(i) % 5]).collect();
+                    let expression = Expression {
+                        operands: &equation.operands,
+                        operators,
+                    };
+                    expression.evaluate() == Some(target)
+                })
+                .count();
+
+            assert_eq!(expected, brute_force, "{target}");
+            assert_eq!(equation.count_solutions(&ALL), brute_force as u64);
+        }
+    }
+}
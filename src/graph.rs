@@ -0,0 +1,361 @@
+//! Graph helpers on top of petgraph's `DiGraphMap`, for grid and dependency puzzles.
+//!
+//! Nodes are small `Copy` values such as grid positions or page numbers. Algorithms that need
+//! an acyclic graph return a [`Cycle`] through the offending nodes instead.
+//!
+//! ```
+//! use advent_of_code::{graph, grid::Grid};
+//!
+//! // Walk downhill only.
+//! let grid = Grid::parse("321\n2.0", |c| c.to_digit(10)).unwrap();
+//! let downhill = graph::from_grid(&grid, |(_, from), (_, to)| match (from, to) {
+//!     (Some(a), Some(b)) => (*b + 1 == *a).then_some(()),
+//!     _ => None,
+//! });
+//!
+//! let path = graph::bfs(&downhill, (0, 0), |pos| grid[pos] == Some(0)).unwrap();
+//! assert_eq!(path, vec![(0, 0), (0, 1), (0, 2), (1, 2)]);
+//!
+//! let paths = graph::count_paths(&downhill, |pos| grid[pos] == Some(0)).unwrap();
+//! assert_eq!(paths[&(0, 0)], 1);
+//! ```
+
+use std::{
+    cmp::Reverse,
+    collections::{hash_map::Entry, BinaryHeap, HashMap, HashSet, VecDeque},
+    fmt::{Debug, Display},
+};
+
+use petgraph::{
+    algo::{self, Measure},
+    graphmap::{DiGraphMap, NodeTrait},
+};
+
+use crate::grid::{Grid, Pos};
+
+/// A directed graph with nodes of type `N` and edge weights of type `E`.
+pub type Graph<N, E = ()> = DiGraphMap<N, E>;
+
+/// A cycle found in a graph that should be acyclic. The last node has an edge to the first.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cycle<N> {
+    pub nodes: Vec<N>,
+}
+
+impl<N: Debug> Display for Cycle<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "cycle through ")?;
+        for node in &self.nodes {
+            write!(f, "{node:?} -> ")?;
+        }
+        match self.nodes.first() {
+            Some(first) => write!(f, "{first:?}"),
+            None => write!(f, "nothing"),
+        }
+    }
+}
+
+impl<N: Debug> std::error::Error for Cycle<N> {}
+
+/* -------------------------------------------------------------------------- */
+
+/// A graph of all cells of `grid`, with an edge to each orthogonal neighbor for which `edge`
+/// returns a weight. `edge` gets the position and value of both cells.
+pub fn from_grid<T, E>(
+    grid: &Grid<T>,
+    mut edge: impl FnMut((Pos, &T), (Pos, &T)) -> Option<E>,
+) -> Graph<Pos, E> {
+    let mut graph = Graph::with_capacity(grid.rows() * grid.cols(), 0);
+
+    for (pos, cell) in grid.iter() {
+        graph.add_node(pos);
+        for next in grid.neighbors4(pos) {
+            if let Some(weight) = edge((pos, cell), (next, &grid[next])) {
+                graph.add_edge(pos, next, weight);
+            }
+        }
+    }
+
+    graph
+}
+
+/// Shortest path, by number of edges, from `start` to the first node for which `goal` holds.
+pub fn bfs<N: NodeTrait, E>(
+    graph: &Graph<N, E>,
+    start: N,
+    mut goal: impl FnMut(N) -> bool,
+) -> Option<Vec<N>> {
+    // Node -> the node it was reached from. The start points to itself.
+    let mut previous = HashMap::from([(start, start)]);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(node) = queue.pop_front() {
+        if goal(node) {
+            let mut path = vec![node];
+            let mut current = node;
+            while current != start {
+                current = previous[&current];
+                path.push(current);
+            }
+            path.reverse();
+            return Some(path);
+        }
+
+        for next in graph.neighbors(node) {
+            if let Entry::Vacant(entry) = previous.entry(next) {
+                entry.insert(node);
+                queue.push_back(next);
+            }
+        }
+    }
+
+    None
+}
+
+/// Cheapest path from `start` to the first node for which `goal` holds, and its cost.
+/// `cost` gives the cost of an edge from its weight.
+pub fn dijkstra<N: NodeTrait, E, K: Measure + Copy>(
+    graph: &Graph<N, E>,
+    start: N,
+    goal: impl FnMut(N) -> bool,
+    cost: impl FnMut(&E) -> K,
+) -> Option<(K, Vec<N>)> {
+    astar(graph, start, goal, cost, |_| K::default())
+}
+
+/// Like [`dijkstra`], guided by `estimate`, which must never overestimate the remaining cost
+/// to a goal, e.g. the Manhattan distance on a grid.
+pub fn astar<N: NodeTrait, E, K: Measure + Copy>(
+    graph: &Graph<N, E>,
+    start: N,
+    goal: impl FnMut(N) -> bool,
+    mut cost: impl FnMut(&E) -> K,
+    estimate: impl FnMut(N) -> K,
+) -> Option<(K, Vec<N>)> {
+    algo::astar(graph, start, goal, |(_, _, weight)| cost(weight), estimate)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// All nodes, each before the nodes it has edges to.
+pub fn toposort<N: NodeTrait, E>(graph: &Graph<N, E>) -> Result<Vec<N>, Cycle<N>> {
+    let nodes: Vec<N> = graph.nodes().collect();
+    toposort_subset(graph, &nodes)
+}
+
+/// Sort `nodes` so that each comes before the nodes it has edges to, ignoring all edges to
+/// other nodes. The sort is stable: of the nodes that could come next, the one that comes first
+/// in `nodes` does, so nodes that are already in order keep their order.
+pub fn toposort_subset<N: NodeTrait, E>(
+    graph: &Graph<N, E>,
+    nodes: &[N],
+) -> Result<Vec<N>, Cycle<N>> {
+    let mut seen = HashSet::new();
+    let nodes: Vec<N> = nodes.iter().copied().filter(|&n| seen.insert(n)).collect();
+    let index: HashMap<N, usize> = nodes.iter().enumerate().map(|(i, &n)| (n, i)).collect();
+
+    // Track the in-degree of each node, by index. Nodes outside the
+    // subset have no index.
+    let mut in_degree = vec![0; nodes.len()];
+    for &node in &nodes {
+        for next in graph.neighbors(node) {
+            if let Some(&i) = index.get(&next) {
+                in_degree[i] += 1;
+            }
+        }
+    }
+
+    // Queue up all nodes with in-degree 0 because nothing comes before them,
+    // earliest index first.
+    let mut queue: BinaryHeap<Reverse<usize>> = (0..nodes.len())
+        .filter(|&i| in_degree[i] == 0)
+        .map(Reverse)
+        .collect();
+
+    let mut sorted = Vec::with_capacity(nodes.len());
+    while let Some(Reverse(i)) = queue.pop() {
+        sorted.push(nodes[i]);
+
+        for next in graph.neighbors(nodes[i]) {
+            if let Some(&j) = index.get(&next) {
+                in_degree[j] -= 1;
+                if in_degree[j] == 0 {
+                    queue.push(Reverse(j));
+                }
+            }
+        }
+    }
+
+    if sorted.len() < nodes.len() {
+        // The nodes that were never queued are on or behind a cycle.
+        let sorted: HashSet<N> = sorted.into_iter().collect();
+        let rest = nodes.iter().copied().filter(|node| !sorted.contains(node));
+        let nodes = find_cycle_among(graph, rest, |node| index.contains_key(&node))
+            .expect("unsorted nodes contain a cycle");
+        return Err(Cycle { nodes });
+    }
+
+    Ok(sorted)
+}
+
+/// A cycle of the graph, if there is one.
+pub fn find_cycle<N: NodeTrait, E>(graph: &Graph<N, E>) -> Option<Cycle<N>> {
+    find_cycle_among(graph, graph.nodes(), |_| true).map(|nodes| Cycle { nodes })
+}
+
+/// Depth-first search from `roots` for a cycle, only following edges to nodes for which `keep`
+/// holds.
+fn find_cycle_among<N: NodeTrait, E>(
+    graph: &Graph<N, E>,
+    roots: impl IntoIterator<Item = N>,
+    keep: impl Fn(N) -> bool,
+) -> Option<Vec<N>> {
+    // Nodes on the current path are `false`, finished nodes `true`.
+    let mut finished: HashMap<N, bool> = HashMap::new();
+
+    for root in roots {
+        if finished.contains_key(&root) {
+            continue;
+        }
+
+        // The current path, with the successors of each node that are left to visit.
+        let mut stack = vec![(root, graph.neighbors(root))];
+        finished.insert(root, false);
+
+        while let Some((node, successors)) = stack.last_mut() {
+            let node = *node;
+            let Some(next) = successors.find(|&next| keep(next)) else {
+                finished.insert(node, true);
+                stack.pop();
+                continue;
+            };
+
+            match finished.get(&next) {
+                // Back to a node on the current path.
+                Some(false) => {
+                    let start = stack.iter().position(|(n, _)| *n == next).unwrap();
+                    return Some(stack[start..].iter().map(|(n, _)| *n).collect());
+                }
+                Some(true) => {}
+                None => {
+                    finished.insert(next, false);
+                    stack.push((next, graph.neighbors(next)));
+                }
+            }
+        }
+    }
+
+    None
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// For every node of an acyclic graph, the nodes for which `target` holds that can be reached
+/// from it, including itself.
+pub fn reachable_targets<N: NodeTrait, E>(
+    graph: &Graph<N, E>,
+    mut target: impl FnMut(N) -> bool,
+) -> Result<HashMap<N, HashSet<N>>, Cycle<N>> {
+    let mut reachable: HashMap<N, HashSet<N>> = HashMap::new();
+
+    // Successors come first, so their sets are complete.
+    for node in toposort(graph)?.into_iter().rev() {
+        let mut targets = HashSet::new();
+        if target(node) {
+            targets.insert(node);
+        }
+        for next in graph.neighbors(node) {
+            targets.extend(&reachable[&next]);
+        }
+        reachable.insert(node, targets);
+    }
+
+    Ok(reachable)
+}
+
+/// For every node of an acyclic graph, the number of paths from it to nodes for which `target`
+/// holds. A target counts as a path to itself, and paths may continue beyond targets.
+pub fn count_paths<N: NodeTrait, E>(
+    graph: &Graph<N, E>,
+    mut target: impl FnMut(N) -> bool,
+) -> Result<HashMap<N, u64>, Cycle<N>> {
+    let mut counts: HashMap<N, u64> = HashMap::new();
+
+    // Successors come first, so their counts are complete.
+    for node in toposort(graph)?.into_iter().rev() {
+        let count =
+            u64::from(target(node)) + graph.neighbors(node).map(|next| counts[&next]).sum::<u64>();
+        counts.insert(node, count);
+    }
+
+    Ok(counts)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 1 -> 2 -> 4, 1 -> 3 -> 4, 4 -> 5
+    fn diamond() -> Graph<u8> {
+        Graph::from_edges([(1, 2), (1, 3), (2, 4), (3, 4), (4, 5)])
+    }
+
+    #[test]
+    fn searches_grids() {
+        let grid = Grid::parse("..#\n#..\n...", |c| c == '#').unwrap();
+        let open = from_grid(&grid, |_, (_, &wall)| (!wall).then_some(1u32));
+
+        let path = bfs(&open, (0, 0), |pos| pos == (2, 0)).unwrap();
+        assert_eq!(path, vec![(0, 0), (0, 1), (1, 1), (2, 1), (2, 0)]);
+        assert_eq!(bfs(&open, (0, 0), |pos| pos == (0, 2)), None);
+
+        let (cost, path) = dijkstra(&open, (0, 0), |pos| pos == (2, 2), |&w| w).unwrap();
+        assert_eq!((cost, path.len()), (4, 5));
+
+        let manhattan = |(row, col): Pos| (2 - row + 2 - col) as u32;
+        let (cost, _) = astar(&open, (0, 0), |pos| pos == (2, 2), |&w| w, manhattan).unwrap();
+        assert_eq!(cost, 4);
+    }
+
+    #[test]
+    fn sorts_topologically() {
+        let graph = diamond();
+        assert_eq!(toposort(&graph), Ok(vec![1, 2, 3, 4, 5]));
+        assert_eq!(toposort_subset(&graph, &[5, 3, 1]), Ok(vec![5, 1, 3]));
+        assert_eq!(toposort_subset(&graph, &[4, 2, 1, 9]), Ok(vec![1, 2, 4, 9]));
+        assert_eq!(toposort_subset(&graph, &[3, 1, 5, 2]), Ok(vec![1, 3, 5, 2]));
+
+        let mut cyclic = graph.clone();
+        cyclic.add_edge(5, 2, ());
+        let cycle = toposort(&cyclic).unwrap_err();
+        assert_eq!(cycle.nodes, vec![2, 4, 5]);
+        assert_eq!(cycle.to_string(), "cycle through 2 -> 4 -> 5 -> 2");
+        assert_eq!(toposort_subset(&cyclic, &[1, 2, 3]), Ok(vec![1, 2, 3]));
+    }
+
+    #[test]
+    fn finds_cycles() {
+        let mut graph = diamond();
+        assert_eq!(find_cycle(&graph), None);
+
+        graph.add_edge(5, 5, ());
+        assert_eq!(find_cycle(&graph), Some(Cycle { nodes: vec![5] }));
+    }
+
+    #[test]
+    fn counts_on_dags() {
+        let graph = diamond();
+        let reachable = reachable_targets(&graph, |n| n >= 4).unwrap();
+        assert_eq!(reachable[&1], HashSet::from([4, 5]));
+        assert_eq!(reachable[&5], HashSet::from([5]));
+
+        let paths = count_paths(&graph, |n| n == 5).unwrap();
+        assert_eq!((paths[&1], paths[&3], paths[&5]), (2, 1, 1));
+
+        let mut cyclic = graph;
+        cyclic.add_edge(4, 1, ());
+        assert!(count_paths(&cyclic, |n| n == 5).is_err());
+    }
+}
@@ -0,0 +1,409 @@
+//! A rectangular 2D grid, e.g. a map of the puzzle input.
+//!
+//! Cells are addressed by `(row, col)`, starting at the top left corner.
+//!
+//! ```
+//! use advent_of_code::grid::Grid;
+//!
+//! let grid = Grid::parse("#.\n.#", |c| c == '#').unwrap();
+//! assert_eq!(grid[(1, 1)], true);
+//! assert_eq!(grid.neighbors4((0, 0)).collect::<Vec<_>>(), vec![(1, 0), (0, 1)]);
+//! assert_eq!(grid.render(|&wall| if wall { '#' } else { '.' }), "#.\n.#\n");
+//! ```
+
+use std::{
+    fmt::Display,
+    ops::{Index, IndexMut},
+};
+
+use crate::{
+    parse::{self, Span},
+    point::{Point, Vector},
+};
+
+/// Position of a cell as `(row, col)`.
+pub type Pos = (usize, usize);
+
+/// The four orthogonal offsets as `(row, col)`: up, left, down and right.
+pub const OFFSETS_4: [(isize, isize); 4] = [(-1, 0), (0, -1), (1, 0), (0, 1)];
+
+/// The four orthogonal and the four diagonal offsets as `(row, col)`, clockwise from the top left.
+pub const OFFSETS_8: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+    (1, 0),
+    (1, -1),
+    (0, -1),
+];
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    rows: usize,
+    cols: usize,
+}
+
+impl<T> Grid<T> {
+    /// A grid of `rows` times `cols` cells, all set to `value`.
+    pub fn new(rows: usize, cols: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            cells: vec![value; rows * cols],
+            rows,
+            cols,
+        }
+    }
+
+    /// A grid from cells in row-major order. Returns `None` if the number of cells does not match.
+    pub fn from_vec(rows: usize, cols: usize, cells: Vec<T>) -> Option<Self> {
+        (cells.len() == rows * cols).then_some(Self { cells, rows, cols })
+    }
+
+    /// Parse a grid with one row per line, mapping each character with `f`.
+    /// Fails if the lines differ in length.
+    pub fn parse(input: &str, mut f: impl FnMut(char) -> T) -> parse::Result<Self> {
+        Self::try_parse(input, |c| Some(f(c)))
+    }
+
+    /// Like [`Grid::parse`], but fails at the first character for which `f` returns `None`.
+    pub fn try_parse(input: &str, mut f: impl FnMut(char) -> Option<T>) -> parse::Result<Self> {
+        let mut cells = vec![];
+        let mut rows = 0;
+        let mut cols = None;
+
+        for line in Span::from(input).lines() {
+            let mut len = 0;
+            for (i, c) in line.text.chars().enumerate() {
+                let Some(cell) = f(c) else {
+                    return Err(at(line, i).error(format!("unexpected character `{c}`")));
+                };
+                cells.push(cell);
+                len += 1;
+            }
+
+            match cols {
+                None => cols = Some(len),
+                Some(cols) if cols != len => {
+                    return Err(at(line, len.min(cols))
+                        .error(format!("expected {cols} columns, found {len}")));
+                }
+                Some(_) => {}
+            }
+            rows += 1;
+        }
+
+        Ok(Self {
+            cells,
+            rows,
+            cols: cols.unwrap_or(0),
+        })
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn get(&self, (row, col): Pos) -> Option<&T> {
+        (row < self.rows && col < self.cols).then(|| &self.cells[row * self.cols + col])
+    }
+
+    pub fn get_mut(&mut self, (row, col): Pos) -> Option<&mut T> {
+        (row < self.rows && col < self.cols).then(|| &mut self.cells[row * self.cols + col])
+    }
+
+    pub fn contains(&self, (row, col): Pos) -> bool {
+        row < self.rows && col < self.cols
+    }
+
+    /// The position of `point`, if it lies within the grid.
+    pub fn pos(&self, point: Point) -> Option<Pos> {
+        let pos = Pos::try_from(point).ok()?;
+        self.contains(pos).then_some(pos)
+    }
+
+    /// The position `offset` away from `pos`, if it lies within the grid.
+    pub fn offset(&self, pos: Pos, offset: impl Into<Vector>) -> Option<Pos> {
+        self.pos(Point::from(pos) + offset.into())
+    }
+
+    /// The orthogonal neighbors of `pos` within the grid, see [`OFFSETS_4`].
+    pub fn neighbors4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        OFFSETS_4
+            .into_iter()
+            .filter_map(move |offset| self.offset(pos, offset))
+    }
+
+    /// The orthogonal and diagonal neighbors of `pos` within the grid, see [`OFFSETS_8`].
+    pub fn neighbors8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        OFFSETS_8
+            .into_iter()
+            .filter_map(move |offset| self.offset(pos, offset))
+    }
+
+    /// All positions in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let cols = self.cols;
+        (0..self.rows).flat_map(move |row| (0..cols).map(move |col| (row, col)))
+    }
+
+    /// All cells and their positions in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.cols..(row + 1) * self.cols]
+    }
+
+    /// Rows from top to bottom.
+    pub fn iter_rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` panics on a chunk size of 0.
+        self.cells.chunks(self.cols.max(1))
+    }
+
+    /// Cells of a column from top to bottom.
+    pub fn column(&self, col: usize) -> impl DoubleEndedIterator<Item = &T> {
+        self.cells[col..].iter().step_by(self.cols).take(self.rows)
+    }
+
+    /// Columns from left to right.
+    pub fn iter_cols(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.cols).map(|col| self.column(col))
+    }
+
+    /// Diagonals running from the top left to the bottom right, starting at the bottom left corner.
+    pub fn diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let starts = (0..self.rows)
+            .rev()
+            .map(|row| (row, 0))
+            .chain((1..self.cols).map(|col| (0, col)));
+        starts.map(|start| self.ray(start, (1, 1)))
+    }
+
+    /// Diagonals running from the bottom left to the top right, starting at the top left corner.
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let last_row = self.rows.saturating_sub(1);
+        let starts = (0..self.rows)
+            .map(|row| (row, 0))
+            .chain((1..self.cols).map(move |col| (last_row, col)));
+        starts.map(|start| self.ray(start, (-1, 1)))
+    }
+
+    /// Cells from `start` (inclusive) in steps of `offset`, until leaving the grid.
+    pub fn ray(&self, start: Pos, offset: impl Into<Vector>) -> impl Iterator<Item = &T> {
+        let offset = offset.into();
+        let first = self.contains(start).then_some(start);
+        std::iter::successors(first, move |&pos| self.offset(pos, offset)).map(|pos| &self[pos])
+    }
+
+    /// Position of the first cell equal to `value`, in row-major order.
+    pub fn find(&self, value: &T) -> Option<Pos>
+    where
+        T: PartialEq,
+    {
+        self.find_all(value).next()
+    }
+
+    /// Positions of all cells equal to `value`, in row-major order.
+    pub fn find_all<'a>(&'a self, value: &'a T) -> impl Iterator<Item = Pos> + 'a
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .filter(move |(_, cell)| *cell == value)
+            .map(|(pos, _)| pos)
+    }
+
+    /// Apply `f` to every cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            rows: self.rows,
+            cols: self.cols,
+        }
+    }
+
+    /// Mirror the grid along its main diagonal, turning rows into columns.
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            cells: self.iter_cols().flatten().cloned().collect(),
+            rows: self.cols,
+            cols: self.rows,
+        }
+    }
+
+    /// Rotate the grid by 90 degrees clockwise.
+    pub fn rotate_cw(&self) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            cells: (0..self.cols)
+                .flat_map(|col| self.column(col).rev())
+                .cloned()
+                .collect(),
+            rows: self.cols,
+            cols: self.rows,
+        }
+    }
+
+    /// Rotate the grid by 90 degrees counterclockwise.
+    pub fn rotate_ccw(&self) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            cells: (0..self.cols)
+                .rev()
+                .flat_map(|col| self.column(col))
+                .cloned()
+                .collect(),
+            rows: self.cols,
+            cols: self.rows,
+        }
+    }
+
+    /// Render the grid as text, one line per row, mapping each cell with `f`.
+    pub fn render(&self, mut f: impl FnMut(&T) -> char) -> String {
+        let mut out = String::with_capacity(self.rows * (self.cols + 1));
+        for row in self.iter_rows().take(self.rows) {
+            out.extend(row.iter().map(&mut f));
+            out.push('\n');
+        }
+        out
+    }
+}
+
+/// The span starting at character `index` of `line`.
+fn at(line: Span, index: usize) -> Span {
+    Span {
+        column: line.column + index,
+        ..line
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (row, col): Pos) -> &T {
+        assert!(
+            row < self.rows && col < self.cols,
+            "position ({row}, {col}) is outside of the {}x{} grid",
+            self.rows,
+            self.cols
+        );
+        &self.cells[row * self.cols + col]
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, (row, col): Pos) -> &mut T {
+        assert!(
+            row < self.rows && col < self.cols,
+            "position ({row}, {col}) is outside of the {}x{} grid",
+            self.rows,
+            self.cols
+        );
+        &mut self.cells[row * self.cols + col]
+    }
+}
+
+/// Renders each cell with its `Display` implementation, one line per row.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.iter_rows().take(self.rows) {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(test)]
+mod tests {
+    use super::Grid;
+    use crate::point::{Direction, Point};
+
+    fn letters() -> Grid<char> {
+        Grid::parse("abc\ndef", |c| c).unwrap()
+    }
+
+    fn collect<'a>(lines: impl Iterator<Item = impl Iterator<Item = &'a char>>) -> Vec<String> {
+        lines.map(|line| line.collect()).collect()
+    }
+
+    #[test]
+    fn parses_and_renders() {
+        let grid = letters();
+        assert_eq!((grid.rows(), grid.cols()), (2, 3));
+        assert_eq!(grid[(1, 0)], 'd');
+        assert_eq!(grid.to_string(), "abc\ndef\n");
+
+        let err = Grid::parse("abc\nde", |c| c).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+
+        let err = Grid::try_parse("..\n.x", |c| (c == '.').then_some(())).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+    }
+
+    #[test]
+    fn iterates_neighbors() {
+        let grid = letters();
+        let neighbors: Vec<char> = grid.neighbors4((0, 1)).map(|pos| grid[pos]).collect();
+        assert_eq!(neighbors, vec!['a', 'e', 'c']);
+
+        let neighbors: Vec<char> = grid.neighbors8((1, 0)).map(|pos| grid[pos]).collect();
+        assert_eq!(neighbors, vec!['a', 'b', 'e']);
+
+        assert_eq!(grid.offset((0, 0), (-1, 0)), None);
+        assert_eq!(grid.offset((0, 0), (1, 2)), Some((1, 2)));
+        assert_eq!(grid.offset((1, 1), Direction::Up), Some((0, 1)));
+        assert_eq!(grid.pos(Point::new(1, 3)), None);
+    }
+
+    #[test]
+    fn iterates_lines() {
+        let grid = letters();
+        let rows: Vec<String> = grid.iter_rows().map(|row| row.iter().collect()).collect();
+        assert_eq!(rows, vec!["abc", "def"]);
+        assert_eq!(collect(grid.iter_cols()), vec!["ad", "be", "cf"]);
+        assert_eq!(collect(grid.diagonals()), vec!["d", "ae", "bf", "c"]);
+        assert_eq!(collect(grid.anti_diagonals()), vec!["a", "db", "ec", "f"]);
+    }
+
+    #[test]
+    fn finds_values() {
+        let grid = Grid::parse("#.#\n..#", |c| c).unwrap();
+        assert_eq!(grid.find(&'#'), Some((0, 0)));
+        assert_eq!(
+            grid.find_all(&'#').collect::<Vec<_>>(),
+            vec![(0, 0), (0, 2), (1, 2)]
+        );
+        assert_eq!(grid.find(&'x'), None);
+    }
+
+    #[test]
+    fn transforms() {
+        let grid = letters();
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf\n");
+        assert_eq!(grid.rotate_cw().to_string(), "da\neb\nfc\n");
+        assert_eq!(grid.rotate_ccw().to_string(), "cf\nbe\nad\n");
+        assert_eq!(grid.rotate_cw().rotate_ccw(), grid);
+    }
+}
@@ -1,3 +1,10 @@
+pub mod equation;
+pub mod graph;
+pub mod grid;
+pub mod parse;
+pub mod patrol;
+pub mod point;
 pub mod template;
+pub mod visualize;
 
 // Use this file to add helper functions and additional modules.
@@ -1,81 +1,176 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, check_answers, download, read, scaffold, solve, time, verify,
+};
 use args::{parse, AppArguments};
 
 #[cfg(feature = "today")]
-use advent_of_code::template::Day;
+use advent_of_code::template::{Day, PuzzleId};
 #[cfg(feature = "today")]
 use std::process;
 
+#[cfg(feature = "all-in-one")]
+mod solutions;
+
 mod args {
-    use advent_of_code::template::Day;
-    use std::process;
+    use advent_of_code::template::{input::Source, runner::BenchConfig, Day, PuzzleId, Year};
+    use advent_of_code::visualize;
+    use std::{process, time::Duration};
 
     pub enum AppArguments {
         Download {
-            day: Day,
+            puzzle: PuzzleId,
         },
         Read {
-            day: Day,
+            puzzle: PuzzleId,
         },
         Scaffold {
-            day: Day,
+            puzzle: PuzzleId,
             download: bool,
             overwrite: bool,
         },
         Solve {
-            day: Day,
+            puzzle: PuzzleId,
             release: bool,
             dhat: bool,
             submit: Option<u8>,
+            input: Source,
+            visualize: Option<visualize::Output>,
+            explain: bool,
         },
         All {
+            year: Year,
+            release: bool,
+            input: Source,
+            jobs: usize,
+        },
+        History {
+            puzzle: PuzzleId,
+        },
+        CheckAnswers {
+            year: Year,
+        },
+        Verify {
+            year: Year,
+            day: Option<Day>,
             release: bool,
         },
         Time {
+            year: Year,
             all: bool,
             day: Option<Day>,
             store: bool,
+            bench: BenchConfig,
+            compare: Option<f64>,
         },
         #[cfg(feature = "today")]
-        Today,
+        Today {
+            year: Year,
+        },
     }
 
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
 
-        let app_args = match args.subcommand()?.as_deref() {
-            Some("all") => AppArguments::All {
-                release: args.contains("--release"),
+        let subcommand = args.subcommand()?;
+
+        // every subcommand works on a single year, which defaults to `AOC_YEAR`.
+        let year: Year = match args.opt_value_from_str("--year")? {
+            Some(year) => year,
+            None => {
+                Year::from_env().ok_or("no year given. Pass `--year <year>` or set `AOC_YEAR`.")?
+            }
+        };
+
+        let app_args = match subcommand.as_deref() {
+            Some("all") => {
+                // every day has its own input, so a single file can not stand in for all of them.
+                if args.contains("--input") {
+                    eprintln!("`all` does not support --input, use --example [<n>] or `solve <day> --input <path>`.");
+                    process::exit(1);
+                }
+
+                AppArguments::All {
+                    year,
+                    release: args.contains("--release"),
+                    jobs: args.opt_value_from_str(["-j", "--jobs"])?.unwrap_or(1),
+                    input: parse_example(&mut args)?.unwrap_or_default(),
+                }
+            }
+            Some("time") if args.contains("--history") => AppArguments::History {
+                puzzle: PuzzleId::new(year, args.free_from_str()?),
             },
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
 
+                let default = BenchConfig::default();
+                let bench = BenchConfig {
+                    budget: args
+                        .opt_value_from_str("--budget")?
+                        .map_or(default.budget, Duration::from_millis),
+                    warmup: args
+                        .opt_value_from_str("--warmup")?
+                        .map_or(default.warmup, Duration::from_millis),
+                    min_samples: args
+                        .opt_value_from_str("--min-samples")?
+                        .unwrap_or(default.min_samples),
+                    max_samples: args
+                        .opt_value_from_str("--max-samples")?
+                        .unwrap_or(default.max_samples),
+                };
+
+                let compare = if args.contains("--compare") {
+                    Some(args.opt_value_from_str("--threshold")?.unwrap_or(10.0))
+                } else {
+                    None
+                };
+
                 AppArguments::Time {
+                    year,
                     all,
                     day: args.opt_free_from_str()?,
                     store,
+                    bench,
+                    compare,
                 }
             }
+            Some("check-answers") => AppArguments::CheckAnswers { year },
+            Some("verify") => AppArguments::Verify {
+                year,
+                release: args.contains("--release"),
+                day: args.opt_free_from_str()?,
+            },
             Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+                puzzle: PuzzleId::new(year, args.free_from_str()?),
             },
             Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
+                puzzle: PuzzleId::new(year, args.free_from_str()?),
             },
             Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
+                puzzle: PuzzleId::new(year, args.free_from_str()?),
                 download: args.contains("--download"),
                 overwrite: args.contains("--overwrite"),
             },
             Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
+                puzzle: PuzzleId::new(year, args.free_from_str()?),
                 release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
                 dhat: args.contains("--dhat"),
+                visualize: match args.opt_value_from_str::<_, String>("--visualize-to")? {
+                    Some(path) => Some(visualize::Output::Images(path.into())),
+                    None => args
+                        .contains("--visualize")
+                        .then_some(visualize::Output::Terminal),
+                },
+                explain: args.contains("--explain"),
+                input: match args.opt_value_from_str::<_, String>("--input")? {
+                    Some(path) if path == "-" => Source::Stdin,
+                    Some(path) => Source::File(path.into()),
+                    None => parse_example(&mut args)?.unwrap_or_default(),
+                },
             },
             #[cfg(feature = "today")]
-            Some("today") => AppArguments::Today,
+            Some("today") => AppArguments::Today { year },
             Some(x) => {
                 eprintln!("Unknown command: {x}");
                 process::exit(1);
@@ -93,42 +188,73 @@ mod args {
 
         Ok(app_args)
     }
+
+    /// Parse `--example [<n>]`. Must be called after all other free arguments were taken.
+    fn parse_example(args: &mut pico_args::Arguments) -> Result<Option<Source>, pico_args::Error> {
+        if args.contains("--example") {
+            Ok(Some(Source::Example(args.opt_free_from_str()?)))
+        } else {
+            Ok(None)
+        }
+    }
 }
 
 fn main() {
+    #[cfg(feature = "all-in-one")]
+    advent_of_code::template::registry::register(solutions::SOLUTIONS);
+
     match parse() {
         Err(err) => {
             eprintln!("Error: {err}");
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold {
+            AppArguments::All {
+                year,
+                release,
+                input,
+                jobs,
+            } => all::handle(year, release, &input, jobs),
+            AppArguments::History { puzzle } => time::history(puzzle),
+            AppArguments::CheckAnswers { year } => check_answers::handle(year),
+            AppArguments::Verify { year, day, release } => verify::handle(year, day, release),
+            AppArguments::Time {
+                year,
                 day,
+                all,
+                store,
+                bench,
+                compare,
+            } => time::handle(year, day, all, store, &bench, compare),
+            AppArguments::Download { puzzle } => download::handle(puzzle),
+            AppArguments::Read { puzzle } => read::handle(puzzle),
+            AppArguments::Scaffold {
+                puzzle,
                 download,
                 overwrite,
             } => {
-                scaffold::handle(day, overwrite);
+                scaffold::handle(puzzle, overwrite);
                 if download {
-                    download::handle(day);
+                    download::handle(puzzle);
                 }
             }
             AppArguments::Solve {
-                day,
+                puzzle,
                 release,
                 dhat,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+                input,
+                visualize,
+                explain,
+            } => solve::handle(puzzle, release, dhat, submit, &input, visualize, explain),
             #[cfg(feature = "today")]
-            AppArguments::Today => {
+            AppArguments::Today { year } => {
                 match Day::today() {
                     Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
+                        let puzzle = PuzzleId::new(year, day);
+                        scaffold::handle(puzzle, false);
+                        download::handle(puzzle);
+                        read::handle(puzzle)
                     }
                     None => {
                         eprintln!(
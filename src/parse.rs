@@ -0,0 +1,340 @@
+//! Helpers for parsing puzzle inputs that report where parsing failed.
+//!
+//! Every helper works on a [`Span`], a piece of the input that remembers its position.
+//! Plain `&str`s are converted to spans starting at line 1, column 1.
+//!
+//! ```
+//! use advent_of_code::parse;
+//!
+//! let input = "47|53\n97|13\n\n75,47,61";
+//! let mut sections = parse::sections(input);
+//!
+//! let rules: Vec<(u8, u8)> = parse::pairs(sections.next().unwrap(), "|").unwrap();
+//! assert_eq!(rules, vec![(47, 53), (97, 13)]);
+//!
+//! let updates = parse::lines(sections.next().unwrap(), |line| line.integers::<u8>()).unwrap();
+//! assert_eq!(updates, vec![vec![75, 47, 61]]);
+//!
+//! let err = parse::pairs::<u8, u8>("1|2\n3|x", "|").unwrap_err();
+//! assert_eq!(err.to_string(), "line 2, column 3: invalid value `x`: invalid digit found in string");
+//! ```
+
+use std::{fmt::Display, str::FromStr};
+
+/// A parse error and the position (1-based) where it occurred.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Error {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+impl std::error::Error for Error {}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Integer types that can be extracted with [`Span::integers`].
+pub trait Integer: FromStr<Err: Display> {
+    const SIGNED: bool;
+}
+
+macro_rules! impl_integer {
+    ($signed:literal, $($t:ty),*) => {
+        $(impl Integer for $t {
+            const SIGNED: bool = $signed;
+        })*
+    };
+}
+
+impl_integer!(true, i8, i16, i32, i64, i128, isize);
+impl_integer!(false, u8, u16, u32, u64, u128, usize);
+
+/* -------------------------------------------------------------------------- */
+
+/// A piece of the input together with the position of its first character.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Span<'a> {
+    pub text: &'a str,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl<'a> From<&'a str> for Span<'a> {
+    fn from(text: &'a str) -> Self {
+        Span {
+            text,
+            line: 1,
+            column: 1,
+        }
+    }
+}
+
+impl<'a> Span<'a> {
+    /// An error pointing at the start of this span.
+    pub fn error(self, message: impl Display) -> Error {
+        Error {
+            line: self.line,
+            column: self.column,
+            message: message.to_string(),
+        }
+    }
+
+    /// The part of this span starting at byte `start` and ending before byte `end`.
+    fn slice(self, start: usize, end: usize) -> Span<'a> {
+        let before = &self.text[..start];
+        let (line, column) = match before.rfind('\n') {
+            Some(i) => (
+                self.line + before.matches('\n').count(),
+                before[i + 1..].chars().count() + 1,
+            ),
+            None => (self.line, self.column + before.chars().count()),
+        };
+
+        Span {
+            text: &self.text[start..end],
+            line,
+            column,
+        }
+    }
+
+    /// Offset of `part`, which must be a subslice of this span.
+    fn offset_of(self, part: &str) -> usize {
+        part.as_ptr() as usize - self.text.as_ptr() as usize
+    }
+
+    fn subspan(self, part: &'a str) -> Span<'a> {
+        let start = self.offset_of(part);
+        self.slice(start, start + part.len())
+    }
+
+    pub fn trim(self) -> Span<'a> {
+        self.subspan(self.text.trim())
+    }
+
+    pub fn lines(self) -> impl Iterator<Item = Span<'a>> {
+        self.text.lines().map(move |line| self.subspan(line))
+    }
+
+    /// Split into sections separated by blank lines.
+    pub fn sections(self) -> impl Iterator<Item = Span<'a>> {
+        let mut sections = vec![];
+        // byte range of the current section.
+        let mut current: Option<(usize, usize)> = None;
+
+        for line in self.text.lines() {
+            let start = self.offset_of(line);
+            if line.trim().is_empty() {
+                sections.extend(current.take());
+            } else {
+                let (section_start, _) = current.unwrap_or((start, start));
+                current = Some((section_start, start + line.len()));
+            }
+        }
+        sections.extend(current);
+
+        sections
+            .into_iter()
+            .map(move |(start, end)| self.slice(start, end))
+    }
+
+    pub fn split(self, delimiter: &'a str) -> impl Iterator<Item = Span<'a>> {
+        self.text
+            .split(delimiter)
+            .map(move |part| self.subspan(part))
+    }
+
+    pub fn split_whitespace(self) -> impl Iterator<Item = Span<'a>> {
+        self.text
+            .split_whitespace()
+            .map(move |part| self.subspan(part))
+    }
+
+    /// Split at the first occurrence of `delimiter`.
+    pub fn split_once(self, delimiter: &str) -> Result<(Span<'a>, Span<'a>)> {
+        let Some(i) = self.text.find(delimiter) else {
+            return Err(self.error(format!("expected `{delimiter}`")));
+        };
+
+        Ok((
+            self.slice(0, i),
+            self.slice(i + delimiter.len(), self.text.len()),
+        ))
+    }
+
+    /// Parse the span, ignoring surrounding whitespace.
+    pub fn parse<T: FromStr<Err: Display>>(self) -> Result<T> {
+        let span = self.trim();
+        span.text
+            .parse()
+            .map_err(|e| span.error(format!("invalid value `{}`: {e}", span.text)))
+    }
+
+    /// Extract all integers, skipping anything in between. For signed types, a `-` directly in
+    /// front of a number is its sign, e.g. `p=0,4 v=-3` contains `0`, `4` and `-3`.
+    pub fn integers<T: Integer>(self) -> Result<Vec<T>> {
+        let bytes = self.text.as_bytes();
+        let mut numbers = vec![];
+        let mut i = 0;
+
+        while i < bytes.len() {
+            let is_negative =
+                T::SIGNED && bytes[i] == b'-' && bytes.get(i + 1).is_some_and(u8::is_ascii_digit);
+
+            if !bytes[i].is_ascii_digit() && !is_negative {
+                i += 1;
+                continue;
+            }
+
+            let start = i;
+            i += 1;
+            while i < bytes.len() && bytes[i].is_ascii_digit() {
+                i += 1;
+            }
+            numbers.push(self.slice(start, i).parse()?);
+        }
+
+        Ok(numbers)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Parse every line with `f`, stopping at the first error.
+pub fn lines<'a, T>(
+    input: impl Into<Span<'a>>,
+    f: impl FnMut(Span<'a>) -> Result<T>,
+) -> Result<Vec<T>> {
+    input.into().lines().map(f).collect()
+}
+
+/// Split the input into sections separated by blank lines.
+pub fn sections<'a>(input: impl Into<Span<'a>>) -> impl Iterator<Item = Span<'a>> {
+    input.into().sections()
+}
+
+/// Extract all integers of a line, see [`Span::integers`].
+pub fn integers<'a, T: Integer>(line: impl Into<Span<'a>>) -> Result<Vec<T>> {
+    line.into().integers()
+}
+
+/// Parse a pair of values separated by `delimiter`, e.g. `47|53`.
+pub fn pair<'a, A, B>(line: impl Into<Span<'a>>, delimiter: &str) -> Result<(A, B)>
+where
+    A: FromStr<Err: Display>,
+    B: FromStr<Err: Display>,
+{
+    let (a, b) = line.into().split_once(delimiter)?;
+    Ok((a.parse()?, b.parse()?))
+}
+
+/// Parse one pair per line, see [`pair`].
+pub fn pairs<'a, A, B>(input: impl Into<Span<'a>>, delimiter: &str) -> Result<Vec<(A, B)>>
+where
+    A: FromStr<Err: Display>,
+    B: FromStr<Err: Display>,
+{
+    lines(input, |line| pair(line, delimiter))
+}
+
+/// Parse a record of a key and whitespace-separated values, e.g. `190: 10 19`.
+pub fn record<'a, K, V>(line: impl Into<Span<'a>>, separator: &str) -> Result<(K, Vec<V>)>
+where
+    K: FromStr<Err: Display>,
+    V: FromStr<Err: Display>,
+{
+    let (key, values) = line.into().split_once(separator)?;
+    let values = values
+        .split_whitespace()
+        .map(Span::parse)
+        .collect::<Result<_>>()?;
+    Ok((key.parse()?, values))
+}
+
+/// Parse one record per line, see [`record`].
+pub fn records<'a, K, V>(input: impl Into<Span<'a>>, separator: &str) -> Result<Vec<(K, Vec<V>)>>
+where
+    K: FromStr<Err: Display>,
+    V: FromStr<Err: Display>,
+{
+    lines(input, |line| record(line, separator))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_integers() {
+        assert_eq!(
+            integers::<i32>("p=0,4 v=-3,+2 x-1"),
+            Ok(vec![0, 4, -3, 2, -1])
+        );
+        assert_eq!(integers::<u8>("p=0,4 v=-3"), Ok(vec![0, 4, 3]));
+        assert_eq!(integers::<u64>("no numbers"), Ok(vec![]));
+
+        let err = integers::<u8>("1 256").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 3));
+    }
+
+    #[test]
+    fn splits_sections() {
+        let sections: Vec<Span> = sections("a\nb\n\n\nc\n\nd\n").collect();
+        assert_eq!(
+            sections,
+            vec![
+                Span {
+                    text: "a\nb",
+                    line: 1,
+                    column: 1
+                },
+                Span {
+                    text: "c",
+                    line: 5,
+                    column: 1
+                },
+                Span {
+                    text: "d",
+                    line: 7,
+                    column: 1
+                },
+            ]
+        );
+        assert_eq!(super::sections("").count(), 0);
+    }
+
+    #[test]
+    fn parses_records() {
+        assert_eq!(
+            records::<u64, u64>("190: 10 19\n3267: 81 40 27", ":"),
+            Ok(vec![(190, vec![10, 19]), (3267, vec![81, 40, 27])])
+        );
+
+        let err = records::<u64, u64>("190: 10 19\n3267: 81 4x 27", ":").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 10));
+
+        let err = records::<u64, u64>("190 10 19", ":").unwrap_err();
+        assert_eq!(err.message, "expected `:`");
+    }
+
+    #[test]
+    fn parses_pairs() {
+        assert_eq!(pairs("3   4\n4   3", " "), Ok(vec![(3, 4), (4, 3)]));
+
+        let input = "1|2\n\n3|4\n5|x";
+        let section = super::sections(input).nth(1).unwrap();
+        let err = pairs::<u8, u8>(section, "|").unwrap_err();
+        assert_eq!((err.line, err.column), (4, 3));
+    }
+}
@@ -0,0 +1,456 @@
+//! The guard's patrol of 2024 day 6: the path of the guard, and the new obstacles that make it
+//! walk in a loop, with the loop for each.
+//!
+//! The guard starts at `^` facing up, walks straight ahead and turns right in front of an
+//! obstacle `#`, until it leaves the grid. Positions are `(row, column)`, counted from 0.
+//!
+//! ```
+//! use advent_of_code::patrol::Lab;
+//!
+//! let lab = Lab::parse(".#...\n....#\n.....\n.^.#.").unwrap();
+//! assert!(lab.patrol().leaves());
+//! assert_eq!(lab.loop_obstacles().unwrap(), [(2, 0)]);
+//!
+//! for obstruction in lab.obstructions().unwrap() {
+//!     println!("{obstruction}");
+//! }
+//! ```
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+};
+
+use crate::grid::{Grid, Pos};
+use crate::parse::{self, Span};
+use crate::point::Direction;
+
+/// Where the guard stands and where it faces.
+pub type State = (Pos, Direction);
+
+/// The map of the lab.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Lab {
+    obstacles: Grid<bool>,
+    start: Pos,
+}
+
+/// The states of a guard's walk, in order. Turning on the spot is a step of its own.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Patrol {
+    pub states: Vec<State>,
+    /// Index in `states` where the loop starts, if the guard walks in one.
+    pub loop_start: Option<usize>,
+}
+
+/// The states of a walk in a loop, starting with the first state that repeats.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Loop {
+    pub states: Vec<State>,
+}
+
+/// A new obstacle that makes the guard walk in a loop.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Obstruction {
+    pub pos: Pos,
+    pub cycle: Loop,
+}
+
+impl Patrol {
+    /// Whether the guard leaves the grid, instead of walking in a loop.
+    pub fn leaves(&self) -> bool {
+        self.loop_start.is_none()
+    }
+
+    /// The loop the guard ends up in, if any.
+    pub fn cycle(&self) -> Option<Loop> {
+        self.loop_start.map(|start| Loop {
+            states: self.states[start..].to_vec(),
+        })
+    }
+
+    /// The positions the guard visits, in the order it first reaches them.
+    pub fn positions(&self) -> Vec<Pos> {
+        let mut seen = HashSet::new();
+        self.states
+            .iter()
+            .map(|&(pos, _)| pos)
+            .filter(|&pos| seen.insert(pos))
+            .collect()
+    }
+}
+
+impl Loop {
+    /// The positions where the guard turns, in order.
+    pub fn turns(&self) -> Vec<Pos> {
+        let mut turns: Vec<Pos> = self
+            .states
+            .iter()
+            .zip(self.states.iter().cycle().skip(1))
+            .filter(|((_, dir), (_, next))| dir != next)
+            .map(|(&(pos, _), _)| pos)
+            .collect();
+        // Turning around at a dead end takes two turns on the same spot.
+        turns.dedup();
+        turns
+    }
+}
+
+/// E.g. `loop of 22 steps turning at (1, 4), (1, 8), (6, 8), (6, 4)`.
+impl Display for Loop {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "loop of {} steps turning at ", self.states.len())?;
+        for (i, (row, col)) in self.turns().into_iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "({row}, {col})")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for Loop {}
+
+/// E.g. `obstacle at (6, 3): loop of 22 steps turning at ...`.
+impl Display for Obstruction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (row, col) = self.pos;
+        write!(f, "obstacle at ({row}, {col}): {}", self.cycle)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl Lab {
+    /// Read a grid of `.` (open), `#` (obstacle) and a single `^` (the guard).
+    pub fn parse(input: &str) -> parse::Result<Self> {
+        let chars = Grid::try_parse(input, |c| matches!(c, '.' | '#' | '^').then_some(c))?;
+        let start = chars
+            .find(&'^')
+            .ok_or_else(|| Span::from(input).error("guard not found in input"))?;
+
+        Ok(Self {
+            obstacles: chars.map(|&c| c == '#'),
+            start,
+        })
+    }
+
+    /// Whether each cell is an obstacle.
+    pub fn obstacles(&self) -> &Grid<bool> {
+        &self.obstacles
+    }
+
+    /// The initial position of the guard, who faces up.
+    pub fn start(&self) -> Pos {
+        self.start
+    }
+
+    /// Walk the guard from the start, step by step.
+    pub fn patrol(&self) -> Patrol {
+        self.patrol_from((self.start, Direction::Up), None)
+    }
+
+    /// Walk the guard from `state`, step by step, with an additional obstacle at `obstacle`.
+    pub fn patrol_from(&self, state: State, obstacle: Option<Pos>) -> Patrol {
+        let (mut pos, mut dir) = state;
+        let mut states = vec![state];
+        let mut seen = HashMap::from([(state, 0)]);
+
+        while let Some(next) = self.obstacles.offset(pos, dir) {
+            if self.obstacles[next] || obstacle == Some(next) {
+                dir = dir.turn_right();
+            } else {
+                pos = next;
+            }
+
+            if let Some(&start) = seen.get(&(pos, dir)) {
+                return Patrol {
+                    states,
+                    loop_start: Some(start),
+                };
+            }
+            seen.insert((pos, dir), states.len());
+            states.push((pos, dir));
+        }
+
+        Patrol {
+            states,
+            loop_start: None,
+        }
+    }
+
+    /// The positions where a single new obstacle makes the guard walk in a loop, in the order
+    /// the guard reaches them. Fails with the loop if the guard walks in one already.
+    pub fn loop_obstacles(&self) -> Result<Vec<Pos>, Loop> {
+        let jumps = Jumps::new(&self.obstacles);
+        let states = self.obstacles.rows() * self.obstacles.cols() * 4;
+        let mut tried = self.obstacles.map(|_| false);
+        let mut walked = BitSet::new(states);
+        let mut seen = BitSet::new(states);
+        let mut found = Vec::new();
+
+        // Walk the original path step by step. The first time the guard is about to enter a
+        // cell, try placing an obstacle there: the path up to this point stays the same, so
+        // the guard continues from its current state.
+        let (mut pos, mut dir) = (self.start, Direction::Up);
+        tried[pos] = true;
+        walked.insert(jumps.state(pos, dir));
+
+        while let Some(next) = self.obstacles.offset(pos, dir) {
+            if self.obstacles[next] {
+                dir = dir.turn_right();
+            } else {
+                if !tried[next] {
+                    tried[next] = true;
+                    if jumps.loops(pos, dir, next, &mut seen) {
+                        found.push(next);
+                    }
+                }
+                pos = next;
+            }
+
+            if !walked.insert(jumps.state(pos, dir)) {
+                return Err(self.patrol().cycle().expect("the guard walks in a loop"));
+            }
+        }
+
+        Ok(found)
+    }
+
+    /// The loop the guard walks in with a new obstacle at `pos`, if it does.
+    pub fn obstruction(&self, pos: Pos) -> Option<Obstruction> {
+        let patrol = self.patrol_from((self.start, Direction::Up), Some(pos));
+        Some(Obstruction {
+            pos,
+            cycle: patrol.cycle()?,
+        })
+    }
+
+    /// Every new obstacle that makes the guard walk in a loop, with the loop, see
+    /// [`Lab::loop_obstacles`].
+    pub fn obstructions(&self) -> Result<Vec<Obstruction>, Loop> {
+        Ok(self
+            .loop_obstacles()?
+            .into_iter()
+            .filter_map(|pos| self.obstruction(pos))
+            .collect())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Precomputed walks of the guard, to follow a path from obstacle to obstacle instead of step
+/// by step.
+struct Jumps {
+    cols: usize,
+    /// For every cell and direction, the cell where the guard stops in front of the next
+    /// obstacle, or `None` if it leaves the grid.
+    stops: Vec<[Option<Pos>; 4]>,
+}
+
+impl Jumps {
+    fn new(obstacles: &Grid<bool>) -> Self {
+        let cols = obstacles.cols();
+        let cells = obstacles.rows() * cols;
+        let mut stops = vec![[None; 4]; cells];
+
+        // The stop of a cell is the stop of the next cell in the same direction, unless that
+        // one is an obstacle. Going up and left, the next cell comes earlier in row-major
+        // order, so fill those forwards and the others backwards.
+        for dir in Direction::ALL {
+            let forwards = matches!(dir, Direction::Up | Direction::Left);
+            for i in 0..cells {
+                let index = if forwards { i } else { cells - 1 - i };
+                let pos = (index / cols, index % cols);
+                stops[index][dir as usize] = match obstacles.offset(pos, dir) {
+                    None => None,
+                    Some(next) if obstacles[next] => Some(pos),
+                    Some((row, col)) => stops[row * cols + col][dir as usize],
+                };
+            }
+        }
+
+        Self { cols, stops }
+    }
+
+    /// Index of a guard state in a `BitSet`.
+    fn state(&self, (row, col): Pos, dir: Direction) -> usize {
+        (row * self.cols + col) * 4 + dir as usize
+    }
+
+    /// Where the guard walking from `pos` towards `dir` stops, with an additional obstacle at
+    /// `obstacle`.
+    fn stop(&self, pos: Pos, dir: Direction, obstacle: Pos) -> Option<Pos> {
+        let stop = self.stops[pos.0 * self.cols + pos.1][dir as usize];
+        let (row, col) = obstacle;
+
+        // The obstacle matters if it lies ahead of the guard, no further than where the guard
+        // would stop otherwise.
+        let blocks = match dir {
+            Direction::Up => col == pos.1 && row < pos.0 && stop.is_none_or(|s| row >= s.0),
+            Direction::Down => col == pos.1 && row > pos.0 && stop.is_none_or(|s| row <= s.0),
+            Direction::Left => row == pos.0 && col < pos.1 && stop.is_none_or(|s| col >= s.1),
+            Direction::Right => row == pos.0 && col > pos.1 && stop.is_none_or(|s| col <= s.1),
+        };
+        if !blocks {
+            return stop;
+        }
+
+        // Stop in front of the obstacle.
+        Some(match dir {
+            Direction::Up => (row + 1, col),
+            Direction::Down => (row - 1, col),
+            Direction::Left => (row, col + 1),
+            Direction::Right => (row, col - 1),
+        })
+    }
+
+    /// Whether the guard at `pos` facing `dir` ends up in a loop once an obstacle is placed at
+    /// `obstacle`. Only the states where the guard turns are recorded in `seen`.
+    fn loops(&self, mut pos: Pos, mut dir: Direction, obstacle: Pos, seen: &mut BitSet) -> bool {
+        seen.clear();
+        loop {
+            let Some(stop) = self.stop(pos, dir, obstacle) else {
+                return false;
+            };
+            pos = stop;
+            dir = dir.turn_right();
+            if !seen.insert(self.state(pos, dir)) {
+                return true;
+            }
+        }
+    }
+}
+
+/// A fixed-size set of guard states.
+struct BitSet {
+    words: Vec<u64>,
+}
+
+impl BitSet {
+    fn new(len: usize) -> Self {
+        Self {
+            words: vec![0; len.div_ceil(64)],
+        }
+    }
+
+    /// Add `index`, returning whether it was not in the set yet.
+    fn insert(&mut self, index: usize) -> bool {
+        let (word, bit) = (index / 64, 1 << (index % 64));
+        let added = self.words[word] & bit == 0;
+        self.words[word] |= bit;
+        added
+    }
+
+    fn clear(&mut self) {
+        self.words.fill(0);
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = "....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#...";
+
+    #[test]
+    fn walks_the_example() {
+        let lab = Lab::parse(EXAMPLE).unwrap();
+        let patrol = lab.patrol();
+        assert!(patrol.leaves());
+        assert_eq!(patrol.positions().len(), 41);
+        assert_eq!(patrol.states.first(), Some(&((6, 4), Direction::Up)));
+        assert_eq!(patrol.states.last(), Some(&((9, 7), Direction::Down)));
+    }
+
+    #[test]
+    fn finds_obstructions() {
+        let lab = Lab::parse(EXAMPLE).unwrap();
+        let obstructions = lab.obstructions().unwrap();
+        let positions: Vec<Pos> = obstructions.iter().map(|o| o.pos).collect();
+        assert_eq!(positions, [(6, 3), (7, 6), (8, 3), (8, 1), (7, 7), (9, 7)]);
+
+        // With an obstacle left of the start, the guard walks around the upper left.
+        assert_eq!(
+            obstructions[0].cycle.turns(),
+            [(1, 4), (1, 8), (6, 8), (6, 4)]
+        );
+        assert_eq!(
+            obstructions[0].to_string(),
+            "obstacle at (6, 3): loop of 22 steps turning at (1, 4), (1, 8), (6, 8), (6, 4)"
+        );
+    }
+
+    #[test]
+    fn reports_loops() {
+        // Boxed in, the guard turns on the spot.
+        let lab = Lab::parse(".#.\n#^#\n.#.").unwrap();
+        let cycle = lab.patrol().cycle().unwrap();
+        assert_eq!(cycle.states.len(), 4);
+        assert_eq!(cycle.turns(), [(1, 1)]);
+        assert_eq!(lab.loop_obstacles(), Err(cycle));
+
+        assert!(Lab::parse("..\n..").is_err());
+    }
+
+    /// The positions where an obstacle makes the guard loop, by walking the whole path again
+    /// for every one.
+    fn naive_loop_obstacles(lab: &Lab) -> Result<Vec<Pos>, Loop> {
+        let patrol = lab.patrol();
+        if let Some(cycle) = patrol.cycle() {
+            return Err(cycle);
+        }
+
+        Ok(patrol
+            .positions()
+            .into_iter()
+            .filter(|&pos| pos != lab.start())
+            .filter(|&pos| {
+                !lab.patrol_from((lab.start(), Direction::Up), Some(pos))
+                    .leaves()
+            })
+            .collect())
+    }
+
+    /// Random maps with about one obstacle in `density` cells and a guard somewhere.
+    fn map() -> impl Strategy<Value = String> {
+        (1..16usize, 1..16usize, 3..8u32).prop_flat_map(|(rows, cols, density)| {
+            let cells = prop::collection::vec(0..density, rows * cols);
+            (cells, 0..rows * cols).prop_map(move |(cells, guard)| {
+                let mut map = String::new();
+                for (index, cell) in cells.into_iter().enumerate() {
+                    map.push(match (index == guard, cell) {
+                        (true, _) => '^',
+                        (false, 0) => '#',
+                        (false, _) => '.',
+                    });
+                    if index % cols == cols - 1 {
+                        map.push('\n');
+                    }
+                }
+                map
+            })
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn jumps_match_naive_walk(input in map()) {
+            let lab = Lab::parse(&input).unwrap();
+            prop_assert_eq!(lab.loop_obstacles(), naive_loop_obstacles(&lab));
+        }
+    }
+}
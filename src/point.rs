@@ -0,0 +1,227 @@
+//! Points, vectors and directions on a 2D grid.
+//!
+//! Coordinates are signed `(row, col)`, so arithmetic can leave the grid. Convert back with
+//! [`Grid::pos`](crate::grid::Grid::pos) to check that a point lies within a grid.
+//!
+//! ```
+//! use advent_of_code::point::{Direction, Point, Vector};
+//!
+//! let p = Point::from((2, 3));
+//! assert_eq!(p + Direction::Up.into(), Point::new(1, 3));
+//! assert_eq!(p - Point::new(0, 0), Vector::new(2, 3));
+//! assert_eq!(Direction::Up.turn_right(), Direction::Right);
+//! assert_eq!(p.manhattan(Point::new(0, 0)), 5);
+//! ```
+
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use crate::grid::Pos;
+
+/// A location, e.g. of a cell in a [`Grid`](crate::grid::Grid).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point {
+    pub row: isize,
+    pub col: isize,
+}
+
+/// The difference between two points.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Vector {
+    pub row: isize,
+    pub col: isize,
+}
+
+/// One of the four orthogonal directions, with rows growing downwards.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Point {
+    pub const fn new(row: isize, col: isize) -> Self {
+        Self { row, col }
+    }
+
+    pub fn manhattan(self, other: Point) -> usize {
+        self.row.abs_diff(other.row) + self.col.abs_diff(other.col)
+    }
+}
+
+impl Vector {
+    pub const fn new(row: isize, col: isize) -> Self {
+        Self { row, col }
+    }
+
+    /// Length of the vector when only moving orthogonally.
+    pub fn manhattan(self) -> usize {
+        self.row.unsigned_abs() + self.col.unsigned_abs()
+    }
+}
+
+impl Direction {
+    /// Clockwise, starting from `Up`.
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn reverse(self) -> Self {
+        Self::ALL[(self as usize + 2) % 4]
+    }
+
+    /// Unit vector pointing in this direction.
+    pub fn vector(self) -> Vector {
+        match self {
+            Direction::Up => Vector::new(-1, 0),
+            Direction::Right => Vector::new(0, 1),
+            Direction::Down => Vector::new(1, 0),
+            Direction::Left => Vector::new(0, -1),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Pos> for Point {
+    fn from((row, col): Pos) -> Self {
+        Self::new(row as isize, col as isize)
+    }
+}
+
+/// Fails for points with a negative coordinate.
+impl TryFrom<Point> for Pos {
+    type Error = std::num::TryFromIntError;
+
+    fn try_from(point: Point) -> Result<Self, Self::Error> {
+        Ok((point.row.try_into()?, point.col.try_into()?))
+    }
+}
+
+impl From<(isize, isize)> for Vector {
+    fn from((row, col): (isize, isize)) -> Self {
+        Self::new(row, col)
+    }
+}
+
+impl From<Direction> for Vector {
+    fn from(direction: Direction) -> Self {
+        direction.vector()
+    }
+}
+
+impl Add<Vector> for Point {
+    type Output = Point;
+
+    fn add(self, v: Vector) -> Point {
+        Point::new(self.row + v.row, self.col + v.col)
+    }
+}
+
+impl AddAssign<Vector> for Point {
+    fn add_assign(&mut self, v: Vector) {
+        *self = *self + v;
+    }
+}
+
+impl Sub<Vector> for Point {
+    type Output = Point;
+
+    fn sub(self, v: Vector) -> Point {
+        self + -v
+    }
+}
+
+impl SubAssign<Vector> for Point {
+    fn sub_assign(&mut self, v: Vector) {
+        *self = *self - v;
+    }
+}
+
+impl Sub for Point {
+    type Output = Vector;
+
+    fn sub(self, other: Point) -> Vector {
+        Vector::new(self.row - other.row, self.col - other.col)
+    }
+}
+
+impl Add for Vector {
+    type Output = Vector;
+
+    fn add(self, other: Vector) -> Vector {
+        Vector::new(self.row + other.row, self.col + other.col)
+    }
+}
+
+impl Sub for Vector {
+    type Output = Vector;
+
+    fn sub(self, other: Vector) -> Vector {
+        self + -other
+    }
+}
+
+impl Neg for Vector {
+    type Output = Vector;
+
+    fn neg(self) -> Vector {
+        Vector::new(-self.row, -self.col)
+    }
+}
+
+impl Mul<isize> for Vector {
+    type Output = Vector;
+
+    fn mul(self, factor: isize) -> Vector {
+        Vector::new(self.row * factor, self.col * factor)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn does_arithmetic() {
+        let a = Point::new(1, 8);
+        let b = Point::new(2, 5);
+        let v = b - a;
+        assert_eq!(v, Vector::new(1, -3));
+        assert_eq!(a + v * 2, Point::new(3, 2));
+        assert_eq!(a - v, Point::new(0, 11));
+        assert_eq!(v + -v, Vector::default());
+        assert_eq!(a.manhattan(b), v.manhattan());
+    }
+
+    #[test]
+    fn turns() {
+        for d in Direction::ALL {
+            assert_eq!(d.turn_right().turn_left(), d);
+            assert_eq!(d.turn_right().turn_right(), d.reverse());
+            assert_eq!(d.reverse().vector(), -d.vector());
+        }
+        assert_eq!(Direction::Left.turn_right(), Direction::Up);
+    }
+
+    #[test]
+    fn converts_positions() {
+        let p = Point::from((3, 4));
+        assert_eq!(Pos::try_from(p), Ok((3, 4)));
+        assert!(Pos::try_from(p + Vector::new(-4, 0)).is_err());
+    }
+}
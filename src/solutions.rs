@@ -0,0 +1,27 @@
+/// All solutions linked into the runner, enabled with the `all-in-one` feature.
+/// `cargo scaffold` adds new days to the list below.
+use advent_of_code::template::registry::Solution;
+
+macro_rules! register {
+    ($( $module:ident => $path:literal, )*) => {
+        $(
+            #[path = $path]
+            mod $module;
+        )*
+
+        pub static SOLUTIONS: &[&Solution] = &[$( &$module::SOLUTION ),*];
+    };
+}
+
+register! {
+    y2024_d01 => "bin/2024-01.rs",
+    y2024_d02 => "bin/2024-02.rs",
+    y2024_d03 => "bin/2024-03.rs",
+    y2024_d04 => "bin/2024-04.rs",
+    y2024_d05 => "bin/2024-05.rs",
+    y2024_d06 => "bin/2024-06.rs",
+    y2024_d07 => "bin/2024-07.rs",
+    y2024_d08 => "bin/2024-08.rs",
+    y2024_d09 => "bin/2024-09.rs",
+    y2024_d10 => "bin/2024-10.rs",
+}
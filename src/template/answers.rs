@@ -0,0 +1,257 @@
+/// Known correct answers for the real puzzle inputs, used to catch regressions when refactoring a solution.
+///
+/// Answers of a puzzle are stored next to its input, e.g. `data/2024/answers/01.toml`:
+///
+/// ```toml
+/// part_one = 1234
+/// part_two = "abc"
+/// ```
+///
+/// The file is updated automatically after a correct submission, but can also be written by hand.
+use std::{fmt::Display, fs, io, path::PathBuf};
+
+use crate::template::{
+    data_dir,
+    runner::{Answer, PartResult},
+    PuzzleId,
+};
+
+#[derive(Debug)]
+pub enum Error {
+    Parse(String),
+    IO(io::Error),
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parse(e) => write!(f, "invalid answers file: {e}"),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub part_one: Option<String>,
+    pub part_two: Option<String>,
+}
+
+impl Answers {
+    /// Path of the answers of a puzzle, e.g. `data/2024/answers/01.toml`.
+    pub fn path(puzzle: PuzzleId) -> PathBuf {
+        data_dir(puzzle.year)
+            .join("answers")
+            .join(format!("{}.toml", puzzle.day))
+    }
+
+    /// Read the answers of a puzzle. Returns `None` if no answer is known.
+    pub fn read(puzzle: PuzzleId) -> Result<Option<Self>, Error> {
+        let path = Self::path(puzzle);
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        Self::parse(&fs::read_to_string(path)?).map(Some)
+    }
+
+    /// Write the answers of a puzzle, creating the answers directory if necessary.
+    pub fn store(&self, puzzle: PuzzleId) -> Result<(), Error> {
+        let path = Self::path(puzzle);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, self.to_toml())?;
+        Ok(())
+    }
+
+    pub fn parse(content: &str) -> Result<Self, Error> {
+        let table: toml::Table = content.parse().map_err(|e| Error::Parse(format!("{e}")))?;
+
+        let answer = |key: &str| match table.get(key) {
+            None => Ok(None),
+            Some(value) => parse_answer(value)
+                .map(Some)
+                .ok_or_else(|| Error::Parse(format!("`{key}` must be a number or a string."))),
+        };
+
+        Ok(Self {
+            part_one: answer("part_one")?,
+            part_two: answer("part_two")?,
+        })
+    }
+
+    pub fn to_toml(&self) -> String {
+        let mut table = toml::Table::new();
+
+        for (key, answer) in [("part_one", &self.part_one), ("part_two", &self.part_two)] {
+            if let Some(answer) = answer {
+                table.insert(key.into(), answer_to_value(answer));
+            }
+        }
+
+        table.to_string()
+    }
+
+    pub fn get(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_one.as_deref(),
+            2 => self.part_two.as_deref(),
+            _ => None,
+        }
+    }
+
+    pub fn set(&mut self, part: u8, answer: &str) {
+        match part {
+            1 => self.part_one = Some(answer.into()),
+            2 => self.part_two = Some(answer.into()),
+            _ => {}
+        }
+    }
+}
+
+/// Read an answer written either as a number or as a string.
+pub(crate) fn parse_answer(value: &toml::Value) -> Option<String> {
+    match value {
+        toml::Value::String(s) => Some(s.clone()),
+        toml::Value::Integer(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+/// Write numeric answers as numbers, unless that would change how they are printed (e.g. `007`).
+fn answer_to_value(answer: &str) -> toml::Value {
+    match answer.parse::<i64>() {
+        Ok(n) if n.to_string() == answer => toml::Value::Integer(n),
+        _ => toml::Value::String(answer.into()),
+    }
+}
+
+/// Record a correct answer for a puzzle.
+pub fn record(puzzle: PuzzleId, part: u8, answer: &str) -> Result<(), Error> {
+    let mut answers = Answers::read(puzzle)?.unwrap_or_default();
+    answers.set(part, answer);
+    answers.store(puzzle)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The result of checking one part against its known answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Outcome {
+    pub part: u8,
+    pub expected: String,
+    pub actual: Answer,
+}
+
+impl Outcome {
+    pub fn is_pass(&self) -> bool {
+        self.actual.solved() == Some(self.expected.as_str())
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_pass() {
+            write!(f, "Part {}: ✔ {}", self.part, self.expected)
+        } else {
+            write!(
+                f,
+                "Part {}: ✖ expected {}, got {}",
+                self.part, self.expected, self.actual
+            )
+        }
+    }
+}
+
+/// Compare the answers of a run with the known answers. Parts without a known answer are skipped.
+pub fn check(answers: &Answers, results: &[PartResult]) -> Vec<Outcome> {
+    [1, 2]
+        .into_iter()
+        .filter_map(|part| {
+            let expected = answers.get(part)?;
+            let actual = results
+                .iter()
+                .find(|result| result.part == part)
+                .map_or(Answer::Unsolved, |result| result.answer.clone());
+
+            Some(Outcome {
+                part,
+                expected: expected.into(),
+                actual,
+            })
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{check, Answers, Outcome};
+    use crate::template::{
+        runner::{Answer, PartResult},
+        stats::Stats,
+    };
+
+    fn part(part: u8, answer: Option<&str>) -> PartResult {
+        PartResult {
+            part,
+            answer: answer.map_or(Answer::Unsolved, |a| Answer::Solved(a.into())),
+            stats: Stats::default(),
+        }
+    }
+
+    #[test]
+    fn roundtrips_toml() {
+        let answers = Answers {
+            part_one: Some("1234".into()),
+            part_two: Some("007".into()),
+        };
+
+        let toml = answers.to_toml();
+        assert_eq!(toml, "part_one = 1234\npart_two = \"007\"\n");
+        assert_eq!(Answers::parse(&toml).unwrap(), answers);
+
+        assert_eq!(Answers::parse("").unwrap(), Answers::default());
+        assert!(Answers::parse("part_one = 1.5").is_err());
+    }
+
+    #[test]
+    fn checks_known_answers() {
+        let answers = Answers {
+            part_one: Some("11".into()),
+            part_two: Some("31".into()),
+        };
+
+        assert_eq!(
+            check(&answers, &[part(1, Some("11")), part(2, None)]),
+            vec![
+                Outcome {
+                    part: 1,
+                    expected: "11".into(),
+                    actual: Answer::Solved("11".into()),
+                },
+                Outcome {
+                    part: 2,
+                    expected: "31".into(),
+                    actual: Answer::Unsolved,
+                },
+            ]
+        );
+
+        let answers = Answers {
+            part_one: None,
+            part_two: Some("31".into()),
+        };
+        let outcomes = check(&answers, &[part(1, Some("1")), part(2, Some("31"))]);
+        assert_eq!(outcomes.len(), 1);
+        assert!(outcomes[0].is_pass());
+    }
+}
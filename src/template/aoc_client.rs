@@ -0,0 +1,579 @@
+/// Native client for the Advent of Code website.
+///
+/// Talks to the puzzle, input and answer endpoints over HTTP. The session cookie is read from the
+/// `AOC_SESSION` environment variable or from a `.adventofcode.session` file in the home or config
+/// directory. The base URL can be overridden with `AOC_BASE_URL`, e.g. to point at a local mock server.
+use std::{
+    env,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::Duration,
+};
+
+use crate::template::{data_dir, input, Day, PuzzleId, Year};
+
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const SESSION_FILE_NAME: &str = ".adventofcode.session";
+const USER_AGENT: &str = concat!(
+    "github.com/fspoettel/advent-of-code-rust ",
+    env!("CARGO_PKG_NAME"),
+    "/",
+    env!("CARGO_PKG_VERSION")
+);
+
+#[derive(Debug)]
+pub enum AocCommandError {
+    /// No session cookie could be found in the environment or a config file.
+    SessionNotFound,
+    /// The server rejected the session cookie.
+    Unauthorized,
+    /// The server asked us to slow down.
+    RateLimited,
+    /// The puzzle (or its input) is not available (yet).
+    NotFound,
+    /// The server answered with an unexpected status code.
+    BadStatus(u16),
+    /// The request could not be sent or the response could not be read.
+    Network(String),
+    IO(io::Error),
+}
+
+impl Display for AocCommandError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocCommandError::SessionNotFound => write!(
+                f,
+                "no session cookie found. Set `AOC_SESSION` or create \"~/{SESSION_FILE_NAME}\"."
+            ),
+            AocCommandError::Unauthorized => {
+                write!(f, "the session cookie was rejected. Try refreshing it.")
+            }
+            AocCommandError::RateLimited => {
+                write!(f, "rate limited by adventofcode.com. Wait a bit and retry.")
+            }
+            AocCommandError::NotFound => write!(f, "puzzle not found. Is it unlocked yet?"),
+            AocCommandError::BadStatus(status) => {
+                write!(f, "adventofcode.com responded with status {status}.")
+            }
+            AocCommandError::Network(e) => write!(f, "request failed: {e}"),
+            AocCommandError::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<io::Error> for AocCommandError {
+    fn from(e: io::Error) -> Self {
+        AocCommandError::IO(e)
+    }
+}
+
+impl From<ureq::Error> for AocCommandError {
+    fn from(e: ureq::Error) -> Self {
+        match e {
+            ureq::Error::Status(400 | 401 | 403, _) => AocCommandError::Unauthorized,
+            ureq::Error::Status(404, _) => AocCommandError::NotFound,
+            ureq::Error::Status(429, _) => AocCommandError::RateLimited,
+            ureq::Error::Status(status, _) => AocCommandError::BadStatus(status),
+            ureq::Error::Transport(t) => AocCommandError::Network(t.to_string()),
+        }
+    }
+}
+
+/// A puzzle description, rendered to markdown.
+#[derive(Debug)]
+pub struct Puzzle {
+    pub day: Day,
+    pub description: String,
+}
+
+/// The server's reply to an answer submission.
+#[derive(Debug)]
+pub struct Submission {
+    pub verdict: Verdict,
+    pub message: String,
+}
+
+/// The outcome of an answer submission, as parsed from the server's reply.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint whether the answer is too high or too low.
+    Incorrect,
+    /// An answer was submitted too recently; nothing was checked.
+    RateLimited,
+    /// The part was already solved or is not unlocked yet; nothing was checked.
+    WrongLevel,
+    Unknown,
+}
+
+impl Verdict {
+    /// Classify the message of the answer page.
+    pub fn from_message(message: &str) -> Self {
+        if message.contains("That's the right answer") {
+            Verdict::Correct
+        } else if message.contains("That's not the right answer") {
+            if message.contains("too high") {
+                Verdict::TooHigh
+            } else if message.contains("too low") {
+                Verdict::TooLow
+            } else {
+                Verdict::Incorrect
+            }
+        } else if message.contains("You gave an answer too recently") {
+            Verdict::RateLimited
+        } else if message.contains("You don't seem to be solving the right level") {
+            Verdict::WrongLevel
+        } else {
+            Verdict::Unknown
+        }
+    }
+
+    /// Whether the server rejected the answer.
+    pub fn is_wrong(self) -> bool {
+        matches!(
+            self,
+            Verdict::TooHigh | Verdict::TooLow | Verdict::Incorrect
+        )
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::TooHigh => "too_high",
+            Verdict::TooLow => "too_low",
+            Verdict::Incorrect => "incorrect",
+            Verdict::RateLimited => "rate_limited",
+            Verdict::WrongLevel => "wrong_level",
+            Verdict::Unknown => "unknown",
+        }
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Verdict::Correct),
+            "too_high" => Ok(Verdict::TooHigh),
+            "too_low" => Ok(Verdict::TooLow),
+            "incorrect" => Ok(Verdict::Incorrect),
+            "rate_limited" => Ok(Verdict::RateLimited),
+            "wrong_level" => Ok(Verdict::WrongLevel),
+            "unknown" => Ok(Verdict::Unknown),
+            _ => Err(format!("unknown verdict `{s}`.")),
+        }
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Client for the Advent of Code HTTP endpoints of a single year.
+pub struct AocClient {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+    year: Year,
+}
+
+impl AocClient {
+    pub fn new(base_url: &str, session: &str, year: Year) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .user_agent(USER_AGENT)
+            .timeout(Duration::from_secs(30))
+            .build();
+
+        Self {
+            agent,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+            year,
+        }
+    }
+
+    /// Configure a client for a year from `AOC_BASE_URL` and the session cookie.
+    pub fn from_env(year: Year) -> Result<Self, AocCommandError> {
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+        let session = get_session().ok_or(AocCommandError::SessionNotFound)?;
+        Ok(Self::new(&base_url, &session, year))
+    }
+
+    pub fn year(&self) -> Year {
+        self.year
+    }
+
+    /// Fetch the puzzle description of a day. Includes part two once part one is solved.
+    pub fn puzzle(&self, day: Day) -> Result<Puzzle, AocCommandError> {
+        let html = self.get(&self.day_url(day))?;
+        let description = extract_tags(&html, "<article", "</article>")
+            .iter()
+            .map(|article| html_to_markdown(article))
+            .collect::<Vec<_>>()
+            .join("\n\n");
+
+        Ok(Puzzle { day, description })
+    }
+
+    /// Fetch the personal puzzle input of a day.
+    pub fn input(&self, day: Day) -> Result<String, AocCommandError> {
+        self.get(&format!("{}/input", self.day_url(day)))
+    }
+
+    /// Submit an answer for one part of a day.
+    pub fn submit(&self, day: Day, part: u8, answer: &str) -> Result<Submission, AocCommandError> {
+        let html = self
+            .agent
+            .post(&format!("{}/answer", self.day_url(day)))
+            .set("Cookie", &self.cookie())
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])?
+            .into_string()?;
+
+        let message = extract_tags(&html, "<article", "</article>")
+            .first()
+            .map(|article| html_to_markdown(article))
+            .unwrap_or_default();
+
+        Ok(Submission {
+            verdict: Verdict::from_message(&message),
+            message,
+        })
+    }
+
+    fn day_url(&self, day: Day) -> String {
+        format!("{}/{}/day/{}", self.base_url, self.year, day.into_inner())
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+
+    fn get(&self, url: &str) -> Result<String, AocCommandError> {
+        Ok(self
+            .agent
+            .get(url)
+            .set("Cookie", &self.cookie())
+            .call()?
+            .into_string()?)
+    }
+}
+
+/// Fetch the puzzle description, store it in `data/<year>/puzzles` and return it.
+pub fn read(puzzle: PuzzleId) -> Result<Puzzle, AocCommandError> {
+    let client = AocClient::from_env(puzzle.year)?;
+    let description = client.puzzle(puzzle.day)?;
+    write_file(&get_puzzle_path(puzzle), &description.description)?;
+    Ok(description)
+}
+
+/// Download puzzle input and description to `data/<year>/inputs` and `data/<year>/puzzles`.
+pub fn download(puzzle: PuzzleId) -> Result<(), AocCommandError> {
+    let client = AocClient::from_env(puzzle.year)?;
+    let input_path = get_input_path(puzzle);
+    let puzzle_path = get_puzzle_path(puzzle);
+
+    write_file(&input_path, &client.input(puzzle.day)?)?;
+    write_file(&puzzle_path, &client.puzzle(puzzle.day)?.description)?;
+
+    println!("---");
+    println!(
+        "🎄 Successfully wrote input to \"{}\".",
+        input_path.display()
+    );
+    println!(
+        "🎄 Successfully wrote puzzle to \"{}\".",
+        puzzle_path.display()
+    );
+    Ok(())
+}
+
+pub fn submit(puzzle: PuzzleId, part: u8, result: &str) -> Result<Submission, AocCommandError> {
+    AocClient::from_env(puzzle.year)?.submit(puzzle.day, part, result)
+}
+
+/// Write `contents` to `path`, creating its directory if necessary, e.g. for a new year.
+fn write_file(path: &Path, contents: &str) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, contents)
+}
+
+fn get_input_path(puzzle: PuzzleId) -> PathBuf {
+    input::path("inputs", puzzle, None)
+}
+
+fn get_puzzle_path(puzzle: PuzzleId) -> PathBuf {
+    data_dir(puzzle.year)
+        .join("puzzles")
+        .join(format!("{}.md", puzzle.day))
+}
+
+/// Read the session cookie from `AOC_SESSION`, falling back to a session file in the home or config directory.
+fn get_session() -> Option<String> {
+    if let Ok(session) = env::var("AOC_SESSION") {
+        if !session.trim().is_empty() {
+            return Some(session);
+        }
+    }
+
+    let home = env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .map(PathBuf::from);
+
+    let config = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| home.as_ref().map(|h| h.join(".config")));
+
+    [home, config]
+        .into_iter()
+        .flatten()
+        .filter_map(|dir| fs::read_to_string(dir.join(SESSION_FILE_NAME)).ok())
+        .map(|s| s.trim().to_string())
+        .find(|s| !s.is_empty())
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Return the contents of every `open ... close` block in `html`, excluding the tags themselves.
+fn extract_tags<'a>(html: &'a str, open: &str, close: &str) -> Vec<&'a str> {
+    let mut blocks = vec![];
+    let mut rest = html;
+
+    while let Some(start) = rest.find(open) {
+        let Some(content_start) = rest[start..].find('>').map(|i| start + i + 1) else {
+            break;
+        };
+        let Some(end) = rest[content_start..].find(close).map(|i| content_start + i) else {
+            break;
+        };
+        blocks.push(&rest[content_start..end]);
+        rest = &rest[end + close.len()..];
+    }
+
+    blocks
+}
+
+/// Render the small subset of HTML used in puzzle descriptions to markdown.
+fn html_to_markdown(html: &str) -> String {
+    let mut out = String::new();
+    let mut in_pre = false;
+    let mut rest = html;
+
+    while let Some(start) = rest.find('<') {
+        push_text(&mut out, &rest[..start], in_pre);
+
+        let Some(end) = rest[start..].find('>').map(|i| start + i) else {
+            break;
+        };
+        let tag = &rest[start + 1..end];
+        let name = tag
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .find(|s| !s.is_empty())
+            .unwrap_or_default();
+        let is_closing = tag.starts_with('/');
+
+        match (name, is_closing) {
+            ("h2", false) => out.push_str("\n## "),
+            ("h2" | "p" | "ul", true) => out.push_str("\n\n"),
+            ("li", false) => out.push_str("- "),
+            ("li", true) => out.push('\n'),
+            ("pre", false) => {
+                in_pre = true;
+                out.push_str("```\n");
+            }
+            ("pre", true) => {
+                in_pre = false;
+                if !out.ends_with('\n') {
+                    out.push('\n');
+                }
+                out.push_str("```\n\n");
+            }
+            ("code", _) if !in_pre => out.push('`'),
+            ("em", _) => out.push('*'),
+            _ => {}
+        }
+
+        rest = &rest[end + 1..];
+    }
+
+    push_text(&mut out, rest, in_pre);
+
+    let mut out = out
+        .lines()
+        .map(str::trim_end)
+        .collect::<Vec<_>>()
+        .join("\n");
+    while out.contains("\n\n\n") {
+        out = out.replace("\n\n\n", "\n\n");
+    }
+    out.trim().to_string()
+}
+
+fn push_text(out: &mut String, text: &str, in_pre: bool) {
+    let text = decode_entities(text);
+    if in_pre {
+        out.push_str(&text);
+    } else {
+        out.push_str(&text.replace('\n', " "));
+    }
+}
+
+fn decode_entities(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{html_to_markdown, write_file, AocClient, AocCommandError, Verdict};
+    use crate::{day, year};
+    use std::{
+        env, fs,
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread::{self, JoinHandle},
+    };
+
+    /// Serve a single canned HTTP response and return the raw request that was received.
+    fn mock_server(status: &str, body: &'static str) -> (String, JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let status = status.to_string();
+
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+            let mut request = String::new();
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(len) = line.to_lowercase().strip_prefix("content-length:") {
+                    content_length = len.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+            let mut body_buf = vec![0; content_length];
+            reader.read_exact(&mut body_buf).unwrap();
+            request.push_str(&String::from_utf8(body_buf).unwrap());
+
+            write!(
+                stream,
+                "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+            request
+        });
+
+        (base_url, handle)
+    }
+
+    #[test]
+    fn fetches_input_with_session_cookie() {
+        let (base_url, server) = mock_server("200 OK", "1 2\n3 4\n");
+        let client = AocClient::new(&base_url, "abc", year!(2024));
+
+        assert_eq!(client.input(day!(1)).unwrap(), "1 2\n3 4\n");
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("GET /2024/day/1/input "));
+        assert!(request.contains("session=abc"));
+    }
+
+    #[test]
+    fn submits_answer_as_form() {
+        let (base_url, server) = mock_server(
+            "200 OK",
+            "<main><article><p>That's the right answer!</p></article></main>",
+        );
+        let client = AocClient::new(&base_url, "abc", year!(2024));
+
+        let submission = client.submit(day!(7), 2, "42").unwrap();
+        assert_eq!(submission.message, "That's the right answer!");
+        assert_eq!(submission.verdict, Verdict::Correct);
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /2024/day/7/answer "));
+        assert!(request.ends_with("level=2&answer=42"));
+    }
+
+    #[test]
+    fn maps_status_codes_to_errors() {
+        let (base_url, _) = mock_server("429 Too Many Requests", "");
+        let client = AocClient::new(&base_url, "abc", year!(2024));
+        assert!(matches!(
+            client.input(day!(1)),
+            Err(AocCommandError::RateLimited)
+        ));
+
+        let (base_url, _) = mock_server("400 Bad Request", "");
+        let client = AocClient::new(&base_url, "abc", year!(2024));
+        assert!(matches!(
+            client.input(day!(1)),
+            Err(AocCommandError::Unauthorized)
+        ));
+    }
+
+    #[test]
+    fn parses_verdicts() {
+        assert_eq!(
+            Verdict::from_message("That's not the right answer; your answer is too high."),
+            Verdict::TooHigh
+        );
+        assert_eq!(
+            Verdict::from_message("That's not the right answer; your answer is too low."),
+            Verdict::TooLow
+        );
+        assert_eq!(
+            Verdict::from_message("That's not the right answer. If you're stuck, ..."),
+            Verdict::Incorrect
+        );
+        assert_eq!(
+            Verdict::from_message("You gave an answer too recently; You have 37s left to wait."),
+            Verdict::RateLimited
+        );
+        assert_eq!(
+            Verdict::from_message("You don't seem to be solving the right level."),
+            Verdict::WrongLevel
+        );
+    }
+
+    #[test]
+    fn renders_puzzle_html() {
+        let html = "<h2>--- Day 1: Test ---</h2><p>Find <em>the</em> <code>sum</code> of\n&lt;x&gt;:</p><pre><code>1 2\n3 4\n</code></pre><ul><li>one</li><li>two</li></ul>";
+        assert_eq!(
+            html_to_markdown(html),
+            "## --- Day 1: Test ---\n\nFind *the* `sum` of <x>:\n\n```\n1 2\n3 4\n```\n\n- one\n- two"
+        );
+    }
+
+    #[test]
+    fn creates_missing_data_directories() {
+        let dir = env::temp_dir().join(format!("aoc-client-{}", std::process::id()));
+        let path = dir.join("2031").join("inputs").join("01.txt");
+
+        write_file(&path, "1 2\n").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1 2\n");
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,245 @@
+/// Builds the solution binaries once and locates the compiled artifacts.
+use std::{
+    collections::HashMap,
+    env, fs, io,
+    io::{BufRead, BufReader},
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
+use tinyjson::JsonValue;
+
+use crate::template::PuzzleId;
+
+/// The cargo profile to build the solutions with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Profile {
+    Debug,
+    Release,
+    /// Release build with debug info and the `dhat-heap` feature enabled.
+    Dhat,
+}
+
+impl Profile {
+    fn cargo_args(self) -> &'static [&'static str] {
+        match self {
+            Profile::Debug => &[],
+            Profile::Release => &["--release"],
+            Profile::Dhat => &["--profile", "dhat", "--features", "dhat-heap"],
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Debug)]
+pub enum Error {
+    BuildFailed,
+    IO(io::Error),
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+/// Paths to the compiled solution binaries, by target name.
+#[derive(Debug, Default)]
+pub struct Bins {
+    paths: HashMap<String, PathBuf>,
+}
+
+impl Bins {
+    /// The binary of a puzzle, if it has been scaffolded.
+    pub fn get(&self, puzzle: PuzzleId) -> Option<&Path> {
+        self.paths.get(&puzzle.to_string()).map(PathBuf::as_path)
+    }
+}
+
+/// Run `cargo build` once for all day binaries and collect the executables from cargo's JSON
+/// messages. The runner itself is not rebuilt, as it may be the executable that is running.
+/// `features` are enabled in addition to the ones of the profile.
+/// If the sources or cargo are not available, e.g. when running from a prebuilt tarball, the day
+/// binaries are expected to sit next to the current executable.
+pub fn build_bins(profile: Profile, features: &[&str], quiet: bool) -> Result<Bins, Error> {
+    build_bins_from(&bin_dir(), profile, features, quiet)
+}
+
+/// Like [`build_bins`], with the day sources in `bin_dir`.
+fn build_bins_from(
+    bin_dir: &Path,
+    profile: Profile,
+    features: &[&str],
+    quiet: bool,
+) -> Result<Bins, Error> {
+    let Ok(targets) = day_targets(bin_dir) else {
+        return prebuilt_bins();
+    };
+    if targets.is_empty() {
+        return Ok(Bins::default());
+    }
+
+    let mut args = vec!["build", "--message-format=json-render-diagnostics"];
+    for target in &targets {
+        args.extend(["--bin", target]);
+    }
+    args.extend(profile.cargo_args());
+    for feature in features {
+        args.extend(["--features", feature]);
+    }
+    if quiet {
+        args.push("--quiet");
+    }
+
+    let mut cmd = match Command::new("cargo")
+        .args(&args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .spawn()
+    {
+        Ok(cmd) => cmd,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return prebuilt_bins(),
+        Err(e) => return Err(e.into()),
+    };
+
+    let stdout = BufReader::new(
+        cmd.stdout
+            .take()
+            .ok_or_else(|| io::Error::from(io::ErrorKind::BrokenPipe))?,
+    );
+
+    let mut paths = HashMap::new();
+    for line in stdout.lines() {
+        if let Some((name, path)) = parse_artifact(&line?) {
+            paths.insert(name, path);
+        }
+    }
+
+    if !cmd.wait()?.success() {
+        return Err(Error::BuildFailed);
+    }
+
+    Ok(Bins { paths })
+}
+
+/// The `src/bin` directory of the crate.
+fn bin_dir() -> PathBuf {
+    let manifest_dir = env::var_os("CARGO_MANIFEST_DIR")
+        .map_or_else(|| PathBuf::from(env!("CARGO_MANIFEST_DIR")), PathBuf::from);
+    manifest_dir.join("src").join("bin")
+}
+
+/// The names of the day binaries in `bin_dir`, e.g. `2024-01`.
+fn day_targets(bin_dir: &Path) -> io::Result<Vec<String>> {
+    let mut targets: Vec<String> = fs::read_dir(bin_dir)?
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let path = entry.path();
+            if path.extension()? != "rs" {
+                return None;
+            }
+            let name = path.file_stem()?.to_str()?;
+            name.parse::<PuzzleId>().ok()?;
+            Some(name.to_string())
+        })
+        .collect();
+    targets.sort();
+
+    Ok(targets)
+}
+
+/// Look for solution binaries, named like `2024-01`, in the directory of the current executable.
+fn prebuilt_bins() -> Result<Bins, Error> {
+    let exe = env::current_exe()?;
+    let dir = exe
+        .parent()
+        .ok_or_else(|| io::Error::from(io::ErrorKind::NotFound))?;
+
+    let paths = fs::read_dir(dir)?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file())
+        .filter_map(|path| {
+            let name = path.file_name()?.to_str()?;
+            let name = name.strip_suffix(env::consts::EXE_SUFFIX)?;
+            name.parse::<PuzzleId>().ok()?;
+            Some((name.to_string(), path))
+        })
+        .collect();
+
+    Ok(Bins { paths })
+}
+
+/// Extract target name and executable path of a `compiler-artifact` message for a bin target.
+fn parse_artifact(line: &str) -> Option<(String, PathBuf)> {
+    let json = line.parse::<JsonValue>().ok()?;
+    let message = json.get::<HashMap<String, JsonValue>>()?;
+
+    if message.get("reason")?.get::<String>()? != "compiler-artifact" {
+        return None;
+    }
+
+    let executable = message.get("executable")?.get::<String>()?;
+    let target = message.get("target")?.get::<HashMap<String, JsonValue>>()?;
+    let is_bin = target
+        .get("kind")?
+        .get::<Vec<JsonValue>>()?
+        .iter()
+        .any(|kind| kind.get::<String>().is_some_and(|k| k == "bin"));
+
+    if !is_bin {
+        return None;
+    }
+
+    let name = target.get("name")?.get::<String>()?;
+    Some((name.clone(), PathBuf::from(executable)))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{bin_dir, build_bins_from, day_targets, parse_artifact, prebuilt_bins, Profile};
+    use std::path::{Path, PathBuf};
+
+    #[test]
+    fn finds_day_targets() {
+        let targets = day_targets(&bin_dir()).unwrap();
+        assert!(targets.contains(&"2024-01".to_string()));
+        assert!(!targets.iter().any(|target| target == "advent_of_code"));
+    }
+
+    #[test]
+    fn uses_prebuilt_bins_without_sources() {
+        let bins = build_bins_from(
+            Path::new("/nonexistent/src/bin"),
+            Profile::Release,
+            &[],
+            true,
+        )
+        .unwrap();
+        assert_eq!(bins.paths, prebuilt_bins().unwrap().paths);
+    }
+
+    #[test]
+    fn parses_bin_artifacts() {
+        let line = r#"{"reason":"compiler-artifact","package_id":"advent_of_code 0.11.0","target":{"kind":["bin"],"crate_types":["bin"],"name":"2024-01","src_path":"/aoc/src/bin/2024-01.rs"},"filenames":["/aoc/target/release/2024-01"],"executable":"/aoc/target/release/2024-01","fresh":true}"#;
+        assert_eq!(
+            parse_artifact(line),
+            Some((
+                "2024-01".into(),
+                PathBuf::from("/aoc/target/release/2024-01")
+            ))
+        );
+    }
+
+    #[test]
+    fn skips_other_messages() {
+        let lib = r#"{"reason":"compiler-artifact","target":{"kind":["lib"],"name":"advent_of_code"},"executable":null,"fresh":true}"#;
+        assert_eq!(parse_artifact(lib), None);
+        assert_eq!(
+            parse_artifact(r#"{"reason":"build-finished","success":true}"#),
+            None
+        );
+        assert_eq!(parse_artifact("not json"), None);
+    }
+}
@@ -0,0 +1,74 @@
+use std::process;
+
+use crate::template::answers::{self, Answers};
+use crate::template::{all_days, input, run_multi::run_quiet, PuzzleId, Year};
+use crate::template::{ANSI_BOLD, ANSI_RESET};
+
+/// Run every day with known answers on its real input and compare the results.
+/// Solutions are always built in release mode. Exits with a non-zero status if any answer differs.
+pub fn handle(year: Year) {
+    let mut puzzles = vec![];
+
+    for day in all_days() {
+        let puzzle = PuzzleId::new(year, day);
+        match Answers::read(puzzle) {
+            Ok(Some(answers)) => {
+                let input = input::path("inputs", puzzle, None);
+                if input.exists() {
+                    puzzles.push((puzzle, answers));
+                } else {
+                    eprintln!("Skipping day {day}: \"{}\" is missing.", input.display());
+                }
+            }
+            Ok(None) => {}
+            Err(e) => {
+                eprintln!("Failed to read answers of day {day}: {e}");
+                process::exit(1);
+            }
+        }
+    }
+
+    if puzzles.is_empty() {
+        println!("No known answers found for {year}.");
+        return;
+    }
+
+    let to_run: Vec<PuzzleId> = puzzles.iter().map(|(puzzle, _)| *puzzle).collect();
+    let mut failed = vec![];
+
+    let ran = run_quiet(&to_run, true, |puzzle, results| {
+        if puzzle != to_run[0] {
+            println!();
+        }
+        println!("{ANSI_BOLD}Day {}{ANSI_RESET}", puzzle.day);
+        println!("------");
+
+        let (_, known) = puzzles.iter().find(|(p, _)| *p == puzzle).unwrap();
+        let outcomes = answers::check(known, &results);
+
+        for outcome in &outcomes {
+            if outcome.is_pass() {
+                println!("{outcome}");
+            } else {
+                println!("{ANSI_BOLD}{outcome}{ANSI_RESET}");
+            }
+        }
+
+        if !outcomes.iter().all(answers::Outcome::is_pass) {
+            failed.push(puzzle.day);
+        }
+    });
+
+    if ran.is_none() {
+        process::exit(1);
+    }
+
+    println!();
+    if failed.is_empty() {
+        println!("All answers match.");
+    } else {
+        let days: Vec<String> = failed.iter().map(ToString::to_string).collect();
+        println!("Answers changed for day(s) {}.", days.join(", "));
+        process::exit(1);
+    }
+}
@@ -1,6 +1,8 @@
 pub mod all;
+pub mod check_answers;
 pub mod download;
 pub mod read;
 pub mod scaffold;
 pub mod solve;
 pub mod time;
+pub mod verify;
@@ -1,15 +1,18 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
+    path::Path,
     process,
 };
 
-use crate::template::Day;
+use crate::template::{examples::Manifest, input, PuzzleId, Year};
 
 const MODULE_TEMPLATE: &str =
     include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
 
-fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
+const SOLUTIONS_PATH: &str = "src/solutions.rs";
+
+fn safe_create_file(path: &Path, overwrite: bool) -> Result<File, std::io::Error> {
     let mut file = OpenOptions::new();
     if overwrite {
         file.create(true);
@@ -19,7 +22,38 @@ fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error>
     file.truncate(true).write(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
+/// Add the day to the solutions linked into the `all-in-one` runner, keeping the list sorted.
+fn register_solution(puzzle: PuzzleId) -> Result<bool, std::io::Error> {
+    let content = fs::read_to_string(SOLUTIONS_PATH)?;
+    let entry = format!(
+        "    y{}_d{} => \"bin/{puzzle}.rs\",",
+        puzzle.year, puzzle.day
+    );
+
+    if content.lines().any(|line| line == entry) {
+        return Ok(false);
+    }
+
+    let mut lines: Vec<&str> = content.lines().collect();
+    let start = lines
+        .iter()
+        .position(|line| *line == "register! {")
+        .ok_or(std::io::ErrorKind::InvalidData)?
+        + 1;
+    let end = start
+        + lines[start..]
+            .iter()
+            .position(|line| *line == "}")
+            .ok_or(std::io::ErrorKind::InvalidData)?;
+
+    let index = start + lines[start..end].partition_point(|line| *line < entry.as_str());
+    lines.insert(index, &entry);
+
+    fs::write(SOLUTIONS_PATH, lines.join("\n") + "\n")?;
+    Ok(true)
+}
+
+fn create_file(path: &Path) -> Result<File, std::io::Error> {
     OpenOptions::new()
         .write(true)
         .create(true)
@@ -27,12 +61,20 @@ fn create_file(path: &str) -> Result<File, std::io::Error> {
         .open(path)
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+pub fn handle(puzzle: PuzzleId, overwrite: bool) {
+    let PuzzleId { year, day } = puzzle;
+    let input_path = input::path("inputs", puzzle, None);
+    let example_path = input::path("examples", puzzle, None);
+    let module_path = format!("src/bin/{puzzle}.rs");
+
+    for path in [&input_path, &example_path] {
+        if let Err(e) = fs::create_dir_all(path.parent().unwrap()) {
+            eprintln!("Failed to create data directory: {e}");
+            process::exit(1);
+        }
+    }
 
-    let mut file = match safe_create_file(&module_path, overwrite) {
+    let mut file = match safe_create_file(Path::new(&module_path), overwrite) {
         Ok(file) => file,
         Err(e) => {
             eprintln!("Failed to create module file: {e}");
@@ -42,6 +84,7 @@ pub fn handle(day: Day, overwrite: bool) {
 
     match file.write_all(
         MODULE_TEMPLATE
+            .replace("%YEAR%", &year.to_string())
             .replace("%DAY_NUMBER%", &day.into_inner().to_string())
             .as_bytes(),
     ) {
@@ -54,9 +97,19 @@ pub fn handle(day: Day, overwrite: bool) {
         }
     }
 
+    match register_solution(puzzle) {
+        Ok(true) => {
+            println!("Registered module in \"{SOLUTIONS_PATH}\"");
+        }
+        Ok(false) => {}
+        Err(e) => {
+            eprintln!("Failed to register module in \"{SOLUTIONS_PATH}\": {e}");
+        }
+    }
+
     match create_file(&input_path) {
         Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
+            println!("Created empty input file \"{}\"", input_path.display());
         }
         Err(e) => {
             eprintln!("Failed to create input file: {e}");
@@ -66,7 +119,7 @@ pub fn handle(day: Day, overwrite: bool) {
 
     match create_file(&example_path) {
         Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
+            println!("Created empty example file \"{}\"", example_path.display());
         }
         Err(e) => {
             eprintln!("Failed to create example file: {e}");
@@ -74,6 +127,26 @@ pub fn handle(day: Day, overwrite: bool) {
         }
     }
 
+    let manifest_path = Manifest::path(puzzle);
+    match safe_create_file(&manifest_path, overwrite) {
+        Ok(mut file) => {
+            let manifest = format!(
+                "[[example]]\nfile = \"{day}.txt\"\n# part_one = <answer>\n# part_two = <answer>\n"
+            );
+            match file.write_all(manifest.as_bytes()) {
+                Ok(()) => println!("Created example manifest \"{}\"", manifest_path.display()),
+                Err(e) => eprintln!("Failed to write example manifest: {e}"),
+            }
+        }
+        Err(e) => {
+            eprintln!("Failed to create example manifest: {e}");
+        }
+    }
+
     println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    if Year::from_env() == Some(year) {
+        println!("🎄 Type `cargo solve {day}` to run your solution.");
+    } else {
+        println!("🎄 Type `cargo solve {day} --year {year}` to run your solution.");
+    }
 }
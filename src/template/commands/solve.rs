@@ -1,29 +1,60 @@
-use std::process::{Command, Stdio};
+use std::process::{self, Command, Stdio};
 
-use crate::template::Day;
+use crate::template::build::{self, Profile};
+use crate::template::{input::Source, PuzzleId};
+use crate::visualize;
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
-
-    if dhat {
-        cmd_args.extend([
-            "--profile".to_string(),
-            "dhat".to_string(),
-            "--features".to_string(),
-            "dhat-heap".to_string(),
-        ]);
+pub fn handle(
+    puzzle: PuzzleId,
+    release: bool,
+    dhat: bool,
+    submit_part: Option<u8>,
+    input: &Source,
+    visualize: Option<visualize::Output>,
+    explain: bool,
+) {
+    let profile = if dhat {
+        Profile::Dhat
     } else if release {
-        cmd_args.push("--release".to_string());
+        Profile::Release
+    } else {
+        Profile::Debug
+    };
+
+    let features: &[&str] = match visualize {
+        Some(_) => &["visualize"],
+        None => &[],
+    };
+
+    let Ok(bins) = build::build_bins(profile, features, false) else {
+        eprintln!("Failed to build solutions.");
+        process::exit(1);
+    };
+
+    let Some(bin) = bins.get(puzzle) else {
+        let PuzzleId { year, day } = puzzle;
+        eprintln!(
+            "Could not find a solution for day {day} of {year}. Use `cargo scaffold {day} --year {year}` to create one."
+        );
+        process::exit(1);
+    };
+
+    let mut cmd_args = input.to_args();
+
+    if let Some(visualize) = visualize {
+        cmd_args.extend(visualize.to_args());
     }
 
-    cmd_args.push("--".to_string());
+    if explain {
+        cmd_args.push("--explain".to_string());
+    }
 
     if let Some(submit_part) = submit_part {
         cmd_args.push("--submit".to_string());
         cmd_args.push(submit_part.to_string());
     }
 
-    let mut cmd = Command::new("cargo")
+    let mut cmd = Command::new(bin)
         .args(&cmd_args)
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
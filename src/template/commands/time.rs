@@ -1,16 +1,34 @@
 use std::collections::HashSet;
+use std::process;
 
+use crate::template::compare;
+use crate::template::history::{format_timestamp, RunInfo};
+use crate::template::input::Source;
 use crate::template::run_multi::run_multi;
-use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::runner::BenchConfig;
+use crate::template::stats::to_duration;
+use crate::template::timings::{Measure, Timings};
+use crate::template::{all_days, readme_benchmarks, Day, PuzzleId, Year, ANSI_BOLD, ANSI_RESET};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
+/// Bench the selected days. If `compare_threshold` is set, compare the results against the stored
+/// timings and exit with a non-zero status if any part regressed by more than that many percent.
+pub fn handle(
+    year: Year,
+    day: Option<Day>,
+    run_all: bool,
+    store: bool,
+    bench: &BenchConfig,
+    compare_threshold: Option<f64>,
+) {
+    let stored_timings = Timings::read_from_file(year);
 
     let days_to_run = day.map_or_else(
         || {
             if run_all {
                 all_days().collect()
+            } else if compare_threshold.is_some() {
+                // when comparing, run the days that have a baseline.
+                stored_timings.data.iter().map(|t| t.day).collect()
             } else {
                 // when the `--all` flag is not set, filter out days that are fully benched.
                 all_days()
@@ -21,14 +39,20 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let timings = run_multi(year, &days_to_run, true, &Source::Puzzle, Some(bench), 1).unwrap();
+
+    let has_regressed = compare_threshold.is_some_and(|threshold| {
+        println!();
+        compare::print_report(&compare::compare(&stored_timings, &timings), threshold)
+    });
 
     if store {
-        let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+        let mut merged_timings = stored_timings.merge(&timings);
+        merged_timings.append_history(&timings, &RunInfo::current("release"));
+        merged_timings.store_file(year).unwrap();
 
         println!();
-        match readme_benchmarks::update(merged_timings) {
+        match readme_benchmarks::update(year, merged_timings) {
             Ok(()) => {
                 println!("Stored updated benchmarks.");
             }
@@ -37,4 +61,59 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
             }
         }
     }
+
+    if has_regressed {
+        process::exit(1);
+    }
+}
+
+/// Print every stored run of a day, with the change of each part relative to the previous run.
+pub fn history(puzzle: PuzzleId) {
+    let PuzzleId { year, day } = puzzle;
+    let timings = Timings::read_from_file(year);
+    let runs: Vec<_> = timings.history_of(day).collect();
+
+    if runs.is_empty() {
+        println!("No stored runs for day {day}. Use `cargo time {day} --store` to record one.");
+        return;
+    }
+
+    println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+    println!("------");
+
+    for (i, run) in runs.iter().enumerate() {
+        let previous = i.checked_sub(1).map(|i| &runs[i].timing);
+
+        let parts = [1, 2]
+            .map(|part| {
+                let Some(nanos) = run.timing.part_nanos(part) else {
+                    return format!("Part {part}: -");
+                };
+
+                let change = previous
+                    .and_then(|p| run.timing.part_nanos_against(p, part))
+                    .filter(|(p, _, _)| *p > 0.0)
+                    .map(|(p, n, measure)| match measure {
+                        Measure::Median => format!(" ({:+.1}%)", (n - p) / p * 100.0),
+                        Measure::Mean => format!(" ({:+.1}% of mean)", (n - p) / p * 100.0),
+                    })
+                    .unwrap_or_default();
+
+                format!("Part {part}: {:.1?}{change}", to_duration(nanos))
+            })
+            .join(", ");
+
+        let commit = run
+            .info
+            .commit
+            .as_deref()
+            .map_or("-------", |c| &c[..c.len().min(7)]);
+
+        println!(
+            "{} {commit} [{}, {}] {parts}",
+            format_timestamp(run.info.timestamp),
+            run.info.profile,
+            run.info.machine
+        );
+    }
 }
@@ -0,0 +1,87 @@
+use std::process::{self, Command};
+
+use crate::template::build::{self, Profile};
+use crate::template::examples::{self, Manifest};
+use crate::template::{all_days, registry, Day, PuzzleId, Year, ANSI_BOLD, ANSI_RESET};
+
+/// Check solutions against the examples in their manifests, either of one day or of every day
+/// that has a manifest. Exits with a non-zero status if any example fails.
+pub fn handle(year: Year, day: Option<Day>, is_release: bool) {
+    let puzzles: Vec<PuzzleId> = match day {
+        Some(day) => vec![PuzzleId::new(year, day)],
+        None => all_days()
+            .map(|day| PuzzleId::new(year, day))
+            .filter(|puzzle| Manifest::path(*puzzle).exists())
+            .collect(),
+    };
+
+    if puzzles.is_empty() {
+        println!("No example manifests found for {year}.");
+        return;
+    }
+
+    let bins = if registry::is_registered() {
+        None
+    } else {
+        let profile = if is_release {
+            Profile::Release
+        } else {
+            Profile::Debug
+        };
+
+        match build::build_bins(profile, &[], true) {
+            Ok(bins) => Some(bins),
+            Err(_) => {
+                eprintln!("Failed to build solutions.");
+                process::exit(1);
+            }
+        }
+    };
+
+    let mut failed = vec![];
+
+    for (i, puzzle) in puzzles.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        println!("{ANSI_BOLD}Day {}{ANSI_RESET}", puzzle.day);
+        println!("------");
+
+        let passed = match &bins {
+            None => match registry::get(*puzzle).map(examples::print_verify) {
+                Some(Ok(passed)) => passed.unwrap_or(true),
+                Some(Err(e)) => {
+                    eprintln!("{e}");
+                    false
+                }
+                None => {
+                    println!("Not solved.");
+                    continue;
+                }
+            },
+            Some(bins) => match bins.get(*puzzle) {
+                Some(bin) => Command::new(bin)
+                    .arg("--verify")
+                    .status()
+                    .is_ok_and(|status| status.success()),
+                None => {
+                    println!("Not solved.");
+                    continue;
+                }
+            },
+        };
+
+        if !passed {
+            failed.push(puzzle.day);
+        }
+    }
+
+    println!();
+    if failed.is_empty() {
+        println!("All examples passed.");
+    } else {
+        let days: Vec<String> = failed.iter().map(ToString::to_string).collect();
+        println!("Examples failed for day(s) {}.", days.join(", "));
+        process::exit(1);
+    }
+}
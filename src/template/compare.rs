@@ -0,0 +1,152 @@
+/// Compares fresh benchmark timings against a stored baseline.
+use crate::template::stats::to_duration;
+use crate::template::timings::{Measure, Timings};
+use crate::template::{Day, ANSI_BOLD, ANSI_GREEN, ANSI_RED, ANSI_RESET};
+
+/// The change in run time of a single part between the baseline and the current run.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartChange {
+    pub day: Day,
+    pub part: u8,
+    pub baseline_nanos: f64,
+    pub current_nanos: f64,
+    /// Medians, unless a timing was stored without sample statistics.
+    pub measure: Measure,
+}
+
+impl PartChange {
+    /// Relative change in percent. Positive values are slowdowns.
+    pub fn change_percent(&self) -> f64 {
+        (self.current_nanos - self.baseline_nanos) / self.baseline_nanos * 100.0
+    }
+
+    pub fn is_regression(&self, threshold_percent: f64) -> bool {
+        self.change_percent() > threshold_percent
+    }
+}
+
+/// Pair every part of `current` with the same part in `baseline`.
+/// Parts without a baseline (or without a current timing) are skipped.
+pub fn compare(baseline: &Timings, current: &Timings) -> Vec<PartChange> {
+    current
+        .data
+        .iter()
+        .filter_map(|timing| Some((timing, baseline.get(timing.day)?)))
+        .flat_map(|(timing, base)| {
+            [1, 2].into_iter().filter_map(|part| {
+                let (baseline_nanos, current_nanos, measure) =
+                    timing.part_nanos_against(base, part)?;
+                (baseline_nanos > 0.0).then_some(PartChange {
+                    day: timing.day,
+                    part,
+                    baseline_nanos,
+                    current_nanos,
+                    measure,
+                })
+            })
+        })
+        .collect()
+}
+
+/// Print a line per part with colored improvement / regression markers.
+/// Returns `true` if any part regressed by more than `threshold_percent`.
+pub fn print_report(changes: &[PartChange], threshold_percent: f64) -> bool {
+    println!("{ANSI_BOLD}Comparison{ANSI_RESET} (threshold: {threshold_percent}%)");
+
+    if changes.is_empty() {
+        println!("No stored timings to compare against.");
+        return false;
+    }
+
+    for change in changes {
+        let percent = change.change_percent();
+        let marker = if change.is_regression(threshold_percent) {
+            format!("{ANSI_RED}▲ {percent:+.1}%{ANSI_RESET}")
+        } else if percent < -threshold_percent {
+            format!("{ANSI_GREEN}▼ {percent:+.1}%{ANSI_RESET}")
+        } else {
+            format!("≈ {percent:+.1}%")
+        };
+
+        let measure = match change.measure {
+            Measure::Median => "",
+            Measure::Mean => " (means, no sample statistics stored)",
+        };
+
+        println!(
+            "Day {} Part {}: {:.1?} -> {:.1?} {marker}{measure}",
+            change.day,
+            change.part,
+            to_duration(change.baseline_nanos),
+            to_duration(change.current_nanos),
+        );
+    }
+
+    let regressions = changes
+        .iter()
+        .filter(|c| c.is_regression(threshold_percent))
+        .count();
+
+    if regressions > 0 {
+        println!("{ANSI_RED}{regressions} part(s) regressed.{ANSI_RESET}");
+    }
+
+    regressions > 0
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::compare;
+    use crate::{
+        day,
+        template::timings::{Measure, Timing, Timings},
+    };
+
+    fn timing(day: crate::template::Day, part_1: Option<&str>, part_2: Option<&str>) -> Timing {
+        Timing {
+            day,
+            part_1: part_1.map(Into::into),
+            part_2: part_2.map(Into::into),
+            part_1_stats: None,
+            part_2_stats: None,
+            part_1_error: None,
+            part_2_error: None,
+            total_nanos: 0.0,
+        }
+    }
+
+    #[test]
+    fn compares_matching_parts() {
+        let baseline = Timings {
+            data: vec![
+                timing(day!(1), Some("100ns"), Some("1.0ms")),
+                timing(day!(2), Some("1.0s"), None),
+            ],
+            history: vec![],
+        };
+        let current = Timings {
+            data: vec![
+                timing(day!(1), Some("120ns"), Some("500.0µs")),
+                timing(day!(2), Some("1.0s"), Some("1.0s")),
+                timing(day!(3), Some("1.0s"), Some("1.0s")),
+            ],
+            history: vec![],
+        };
+
+        let changes = compare(&baseline, &current);
+        assert_eq!(changes.len(), 3);
+
+        assert_eq!((changes[0].day, changes[0].part), (day!(1), 1));
+        assert!((changes[0].change_percent() - 20.0).abs() < 1e-9);
+        assert!(changes[0].is_regression(10.0));
+        assert!(!changes[0].is_regression(25.0));
+
+        assert_eq!((changes[1].day, changes[1].part), (day!(1), 2));
+        assert!((changes[1].change_percent() + 50.0).abs() < 1e-9);
+        assert!(!changes[1].is_regression(10.0));
+
+        assert_eq!((changes[2].day, changes[2].part), (day!(2), 1));
+        assert_eq!(changes[2].change_percent(), 0.0);
+        assert_eq!(changes[2].measure, Measure::Mean);
+    }
+}
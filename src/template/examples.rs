@@ -0,0 +1,417 @@
+/// Example manifests list example inputs of a puzzle together with their expected answers.
+///
+/// A manifest lives next to the example inputs, e.g. `data/2024/examples/01.toml`:
+///
+/// ```toml
+/// [[example]]
+/// file = "01.txt"
+/// part_one = 11
+/// part_two = 31
+///
+/// [[example]]
+/// name = "inline"
+/// input = """
+/// 3   4
+/// """
+/// part_one = 1
+/// ```
+///
+/// Each example either names a `file` in the examples directory or contains its `input` inline.
+/// Expected answers are optional, parts without one are not checked.
+use std::{
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    process,
+};
+
+use crate::template::{
+    answers::parse_answer, data_dir, registry::Solution, runner::Answer, PuzzleId, ANSI_BOLD,
+    ANSI_RESET,
+};
+
+#[derive(Debug)]
+pub enum Error {
+    Parse(String),
+    IO(io::Error),
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parse(e) => write!(f, "invalid example manifest: {e}"),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+/// One example input and the answers it should produce.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Case {
+    pub name: String,
+    pub input: String,
+    pub part_one: Option<String>,
+    pub part_two: Option<String>,
+}
+
+impl Case {
+    pub fn expected(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_one.as_deref(),
+            2 => self.part_two.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Manifest {
+    pub cases: Vec<Case>,
+}
+
+impl Manifest {
+    /// Path of the manifest of a puzzle, e.g. `data/2024/examples/01.toml`.
+    pub fn path(puzzle: PuzzleId) -> PathBuf {
+        data_dir(puzzle.year)
+            .join("examples")
+            .join(format!("{}.toml", puzzle.day))
+    }
+
+    /// Read the manifest of a puzzle. Returns `None` if the puzzle has no manifest.
+    pub fn read(puzzle: PuzzleId) -> Result<Option<Self>, Error> {
+        let path = Self::path(puzzle);
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let content = fs::read_to_string(&path)?;
+        Self::parse(&content, path.parent().unwrap_or(Path::new("."))).map(Some)
+    }
+
+    /// Parse a manifest. Example `file`s are read relative to `dir`.
+    pub fn parse(content: &str, dir: &Path) -> Result<Self, Error> {
+        let table: toml::Table = content.parse().map_err(|e| Error::Parse(format!("{e}")))?;
+
+        let examples = match table.get("example") {
+            Some(toml::Value::Array(examples)) => examples.as_slice(),
+            Some(_) => return Err(Error::Parse("`example` must be an array of tables.".into())),
+            None => &[],
+        };
+
+        let cases = examples
+            .iter()
+            .enumerate()
+            .map(|(i, example)| {
+                let example = example
+                    .as_table()
+                    .ok_or_else(|| Error::Parse(format!("example {} is not a table.", i + 1)))?;
+                parse_case(example, i, dir)
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { cases })
+    }
+}
+
+fn parse_case(example: &toml::Table, index: usize, dir: &Path) -> Result<Case, Error> {
+    let string = |key: &str| match example.get(key) {
+        None => Ok(None),
+        Some(toml::Value::String(s)) => Ok(Some(s.clone())),
+        Some(_) => Err(Error::Parse(format!(
+            "`{key}` of example {} must be a string.",
+            index + 1
+        ))),
+    };
+
+    let answer = |key: &str| match example.get(key) {
+        None => Ok(None),
+        Some(value) => parse_answer(value).map(Some).ok_or_else(|| {
+            Error::Parse(format!(
+                "`{key}` of example {} must be a number or a string.",
+                index + 1
+            ))
+        }),
+    };
+
+    let file = string("file")?;
+    let input = match (&file, string("input")?) {
+        (Some(file), None) => fs::read_to_string(dir.join(file))?,
+        (None, Some(input)) => input,
+        _ => {
+            return Err(Error::Parse(format!(
+                "example {} needs exactly one of `file` or `input`.",
+                index + 1
+            )))
+        }
+    };
+
+    let name = string("name")?
+        .or(file)
+        .unwrap_or_else(|| format!("example {}", index + 1));
+
+    Ok(Case {
+        name,
+        input,
+        part_one: answer("part_one")?,
+        part_two: answer("part_two")?,
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The result of checking one part of one example.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Outcome {
+    pub case: String,
+    pub part: u8,
+    pub expected: String,
+    pub actual: Answer,
+}
+
+impl Outcome {
+    pub fn is_pass(&self) -> bool {
+        self.actual.solved() == Some(self.expected.as_str())
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_pass() {
+            write!(f, "{} Part {}: ✔ {}", self.case, self.part, self.expected)
+        } else {
+            write!(
+                f,
+                "{} Part {}: ✖ expected {}, got {}",
+                self.case, self.part, self.expected, self.actual
+            )
+        }
+    }
+}
+
+/// Run every part of the solution that has an expected answer against every example.
+pub fn verify(solution: &Solution, manifest: &Manifest) -> Vec<Outcome> {
+    let mut outcomes = vec![];
+
+    for case in &manifest.cases {
+        for run_part in solution.parts {
+            let result = run_part(&case.input, None);
+            if let Some(expected) = case.expected(result.part) {
+                outcomes.push(Outcome {
+                    case: case.name.clone(),
+                    part: result.part,
+                    expected: expected.to_string(),
+                    actual: result.answer,
+                });
+            }
+        }
+    }
+
+    outcomes
+}
+
+/// Check the examples of a solution and print the outcomes.
+/// Returns `Ok(None)` if the solution has no manifest, otherwise whether all examples passed.
+pub fn print_verify(solution: &Solution) -> Result<Option<bool>, Error> {
+    let Some(manifest) = Manifest::read(solution.puzzle)? else {
+        return Ok(None);
+    };
+
+    let outcomes = verify(solution, &manifest);
+    for outcome in &outcomes {
+        if outcome.is_pass() {
+            println!("{outcome}");
+        } else {
+            println!("{ANSI_BOLD}{outcome}{ANSI_RESET}");
+        }
+    }
+
+    Ok(Some(outcomes.iter().all(Outcome::is_pass)))
+}
+
+/// Entry point of solution binaries invoked with `--verify`. Exits with a non-zero status if an
+/// example fails or the manifest is invalid.
+pub fn run_verify(solution: &Solution) {
+    match print_verify(solution) {
+        Ok(Some(true)) => {}
+        Ok(None) => println!(
+            "No examples found. Create \"{}\" to add some.",
+            Manifest::path(solution.puzzle).display()
+        ),
+        Ok(Some(false)) => process::exit(1),
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    }
+}
+
+/// Used by [`crate::example_tests`]. Panics with a list of failures if any example fails, and
+/// if there is no manifest, as the test would not check anything.
+pub fn assert_examples(solution: &Solution) {
+    let manifest = match Manifest::read(solution.puzzle) {
+        Ok(Some(manifest)) => manifest,
+        Ok(None) => panic!(
+            "no examples found, create \"{}\" to add some",
+            Manifest::path(solution.puzzle).display()
+        ),
+        Err(e) => panic!("{e}"),
+    };
+
+    let failures: Vec<String> = verify(solution, &manifest)
+        .iter()
+        .filter(|outcome| !outcome.is_pass())
+        .map(ToString::to_string)
+        .collect();
+
+    assert!(
+        failures.is_empty(),
+        "examples failed:\n{}",
+        failures.join("\n")
+    );
+}
+
+/// Creates a test that checks the solution against every example in its manifest.
+/// Invoke it in the test module of a solution, which needs to see `SOLUTION` (e.g. via `use super::*`).
+#[macro_export]
+macro_rules! example_tests {
+    () => {
+        #[test]
+        fn examples() {
+            $crate::template::examples::assert_examples(&SOLUTION);
+        }
+    };
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{assert_examples, verify, Case, Manifest, Outcome};
+    use crate::{
+        day,
+        template::{
+            registry::Solution,
+            runner::{measure_part, Answer},
+            PuzzleId,
+        },
+        year,
+    };
+    use std::{env, fs};
+
+    fn count_lines(input: &str) -> Option<usize> {
+        Some(input.lines().count())
+    }
+
+    fn first_line(input: &str) -> Option<String> {
+        input.lines().next().map(Into::into)
+    }
+
+    static SOLUTION: Solution = Solution {
+        puzzle: PuzzleId::new(year!(2024), day!(1)),
+        parts: &[
+            |input, bench| measure_part(count_lines, input, 1, bench),
+            |input, bench| measure_part(first_line, input, 2, bench),
+        ],
+    };
+
+    #[test]
+    fn parses_manifests() {
+        let dir = env::temp_dir().join(format!("aoc-examples-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("01.txt"), "a\nb\n").unwrap();
+
+        let manifest = Manifest::parse(
+            r#"
+            [[example]]
+            file = "01.txt"
+            part_one = 2
+
+            [[example]]
+            name = "inline"
+            input = "x"
+            part_two = "x"
+            "#,
+            &dir,
+        )
+        .unwrap();
+
+        assert_eq!(
+            manifest.cases,
+            vec![
+                Case {
+                    name: "01.txt".into(),
+                    input: "a\nb\n".into(),
+                    part_one: Some("2".into()),
+                    part_two: None,
+                },
+                Case {
+                    name: "inline".into(),
+                    input: "x".into(),
+                    part_one: None,
+                    part_two: Some("x".into()),
+                },
+            ]
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "no examples found")]
+    fn requires_a_manifest_in_tests() {
+        // there is no data for 2015.
+        static UNKNOWN: Solution = Solution {
+            puzzle: PuzzleId::new(year!(2015), day!(1)),
+            parts: &[|input, bench| measure_part(count_lines, input, 1, bench)],
+        };
+        assert_examples(&UNKNOWN);
+    }
+
+    #[test]
+    fn rejects_invalid_manifests() {
+        let dir = env::temp_dir();
+        assert!(Manifest::parse("example = 1", &dir).is_err());
+        assert!(Manifest::parse("[[example]]\npart_one = 1", &dir).is_err());
+        assert!(Manifest::parse("[[example]]\ninput = \"x\"\npart_one = 1.5", &dir).is_err());
+        assert_eq!(Manifest::parse("", &dir).unwrap(), Manifest::default());
+    }
+
+    #[test]
+    fn verifies_expected_answers() {
+        let manifest = Manifest {
+            cases: vec![Case {
+                name: "small".into(),
+                input: "a\nb".into(),
+                part_one: Some("2".into()),
+                part_two: Some("b".into()),
+            }],
+        };
+
+        let outcomes = verify(&SOLUTION, &manifest);
+        assert_eq!(
+            outcomes,
+            vec![
+                Outcome {
+                    case: "small".into(),
+                    part: 1,
+                    expected: "2".into(),
+                    actual: Answer::Solved("2".into()),
+                },
+                Outcome {
+                    case: "small".into(),
+                    part: 2,
+                    expected: "b".into(),
+                    actual: Answer::Solved("a".into()),
+                },
+            ]
+        );
+        assert!(outcomes[0].is_pass());
+        assert!(!outcomes[1].is_pass());
+    }
+}
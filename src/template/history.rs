@@ -0,0 +1,254 @@
+/// Metadata for benchmark runs kept in the timing history.
+use std::{
+    collections::HashMap,
+    env, fs,
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::timings::Timing;
+
+/// One benchmark run of a day, as appended to the history when storing timings.
+#[derive(Clone, Debug)]
+pub struct Run {
+    pub timing: Timing,
+    pub info: RunInfo,
+}
+
+/// Where and when a benchmark run happened.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RunInfo {
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    /// Commit hash of `HEAD`, if the workspace is a git repository.
+    pub commit: Option<String>,
+    /// Cargo profile the solutions were built with.
+    pub profile: String,
+    /// Identifies the machine, see [`machine_id`].
+    pub machine: String,
+}
+
+impl RunInfo {
+    /// Describe a run happening now in the current workspace.
+    pub fn current(profile: &str) -> Self {
+        Self {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+            commit: env::current_dir().ok().and_then(|dir| git_commit(&dir)),
+            profile: profile.into(),
+            machine: machine_id(),
+        }
+    }
+}
+
+/// Read the commit hash of `HEAD` of the repository containing `dir` straight from its git
+/// directory. `None` if `dir` is not in a repository.
+fn git_commit(dir: &Path) -> Option<String> {
+    let dot_git = dir
+        .ancestors()
+        .map(|dir| dir.join(".git"))
+        .find(|path| path.exists())?;
+
+    // in worktrees and submodules, `.git` is a file pointing to the actual git directory.
+    let git_dir = if dot_git.is_file() {
+        let content = fs::read_to_string(&dot_git).ok()?;
+        let path = content.strip_prefix("gitdir:")?.trim();
+        dot_git.parent()?.join(path)
+    } else {
+        dot_git
+    };
+
+    let head = fs::read_to_string(git_dir.join("HEAD")).ok()?;
+    let head = head.trim();
+
+    let Some(reference) = head.strip_prefix("ref:").map(str::trim) else {
+        // detached HEAD.
+        return Some(head.to_string());
+    };
+
+    // worktrees share the branches of the main git directory, named by `commondir`.
+    let common_dir = match fs::read_to_string(git_dir.join("commondir")) {
+        Ok(path) => git_dir.join(path.trim()),
+        Err(_) => git_dir.clone(),
+    };
+
+    for dir in [&git_dir, &common_dir] {
+        if let Ok(hash) = fs::read_to_string(dir.join(reference)) {
+            return Some(hash.trim().to_string());
+        }
+    }
+
+    // the ref might only exist in packed form.
+    fs::read_to_string(common_dir.join("packed-refs"))
+        .ok()?
+        .lines()
+        .find_map(|line| {
+            let (hash, name) = line.split_once(' ')?;
+            (name == reference).then(|| hash.to_string())
+        })
+}
+
+/// Identify the machine by `AOC_MACHINE` if set, otherwise by host name, OS and architecture.
+fn machine_id() -> String {
+    if let Ok(machine) = env::var("AOC_MACHINE") {
+        return machine;
+    }
+
+    let host = env::var("HOSTNAME")
+        .or_else(|_| env::var("COMPUTERNAME"))
+        .ok()
+        .or_else(|| fs::read_to_string("/etc/hostname").ok())
+        .map(|h| h.trim().to_string())
+        .filter(|h| !h.is_empty())
+        .unwrap_or_else(|| "unknown".into());
+
+    format!("{host} ({}-{})", env::consts::OS, env::consts::ARCH)
+}
+
+/// Format a unix timestamp as a UTC date and time, e.g. `2024-12-08 14:03`.
+pub fn format_timestamp(timestamp: u64) -> String {
+    let days = timestamp / 86400;
+    let secs = timestamp % 86400;
+
+    // civil date from days since epoch, see: https://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}",
+        secs / 3600,
+        secs % 3600 / 60
+    )
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Run> for JsonValue {
+    fn from(value: &Run) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        let info = &value.info;
+
+        map.insert("timing".into(), JsonValue::from(&value.timing));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("timestamp".into(), JsonValue::Number(info.timestamp as f64));
+        map.insert(
+            "commit".into(),
+            info.commit
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert("profile".into(), JsonValue::String(info.profile.clone()));
+        map.insert("machine".into(), JsonValue::String(info.machine.clone()));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Run {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected run to be a JSON object.")?;
+
+        let timing = json
+            .get("timing")
+            .ok_or("Expected run.timing to be present.")
+            .map(Timing::try_from)??;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .map(|t| *t as u64)
+            .ok_or("Expected run.timestamp to be a number.")?;
+
+        let commit = json
+            .get("commit")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected run.commit to be null or string.")?;
+
+        let string = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<String>())
+                .cloned()
+                .ok_or(format!("Expected run.{key} to be a string."))
+        };
+
+        Ok(Run {
+            timing,
+            info: RunInfo {
+                timestamp,
+                commit: commit.cloned(),
+                profile: string("profile")?,
+                machine: string("machine")?,
+            },
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_timestamp, git_commit};
+    use std::{env, fs};
+
+    #[test]
+    fn formats_timestamps() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00");
+        assert_eq!(format_timestamp(1_733_666_580), "2024-12-08 14:03");
+        assert_eq!(format_timestamp(951_782_400), "2000-02-29 00:00");
+    }
+
+    #[test]
+    fn reads_git_commit() {
+        let dir = env::temp_dir().join(format!("aoc-git-{}", std::process::id()));
+        let git_dir = dir.join(".git");
+        fs::create_dir_all(git_dir.join("refs/heads")).unwrap();
+        fs::create_dir_all(dir.join("src")).unwrap();
+
+        fs::write(git_dir.join("HEAD"), "ref: refs/heads/main\n").unwrap();
+        fs::write(git_dir.join("refs/heads/main"), "abc123\n").unwrap();
+        assert_eq!(git_commit(&dir), Some("abc123".into()));
+        assert_eq!(git_commit(&dir.join("src")), Some("abc123".into()));
+
+        fs::remove_file(git_dir.join("refs/heads/main")).unwrap();
+        fs::write(
+            git_dir.join("packed-refs"),
+            "# pack-refs with: peeled\ndef456 refs/heads/main\n",
+        )
+        .unwrap();
+        assert_eq!(git_commit(&dir), Some("def456".into()));
+
+        // a worktree on another branch, with a relative `gitdir`.
+        let worktree = dir.join("worktree");
+        let worktree_git_dir = git_dir.join("worktrees/worktree");
+        fs::create_dir_all(&worktree).unwrap();
+        fs::create_dir_all(&worktree_git_dir).unwrap();
+        fs::write(
+            worktree.join(".git"),
+            "gitdir: ../.git/worktrees/worktree\n",
+        )
+        .unwrap();
+        fs::write(worktree_git_dir.join("commondir"), "../..\n").unwrap();
+        fs::write(worktree_git_dir.join("HEAD"), "ref: refs/heads/feature\n").unwrap();
+        fs::write(git_dir.join("refs/heads/feature"), "789fed\n").unwrap();
+        assert_eq!(git_commit(&worktree), Some("789fed".into()));
+
+        fs::write(git_dir.join("HEAD"), "0123abcd\n").unwrap();
+        assert_eq!(git_commit(&dir), Some("0123abcd".into()));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
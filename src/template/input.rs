@@ -0,0 +1,253 @@
+/// Locating and reading puzzle data.
+///
+/// The data root (`data` by default) is resolved from, in order:
+/// 1. the `AOC_DATA_DIR` environment variable,
+/// 2. the `data_dir` key of an `aoc.toml` file in the current directory or one of its parents,
+/// 3. the `data` directory of this crate (`CARGO_MANIFEST_DIR`).
+///
+/// This allows running solution binaries from any directory.
+use std::{
+    env,
+    ffi::OsString,
+    fmt::Display,
+    fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+use crate::template::{data_dir, PuzzleId};
+
+const DATA_DIR_ENV: &str = "AOC_DATA_DIR";
+const CONFIG_FILE_NAME: &str = "aoc.toml";
+
+#[derive(Debug)]
+pub enum Error {
+    /// A file could not be read. Holds the path that was tried.
+    Read(PathBuf, io::Error),
+    Stdin(io::Error),
+    /// A config file is invalid.
+    Config(PathBuf, String),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Read(path, e) => write!(f, "could not read \"{}\": {e}", path.display()),
+            Error::Stdin(e) => write!(f, "could not read input from stdin: {e}"),
+            Error::Config(path, e) => write!(f, "invalid config \"{}\": {e}", path.display()),
+        }
+    }
+}
+
+/// The root directory of all puzzle data. Resolved once, see the module documentation.
+/// Paths inside the current directory are returned relative to it.
+pub fn data_root() -> &'static Path {
+    static DATA_ROOT: OnceLock<PathBuf> = OnceLock::new();
+
+    DATA_ROOT.get_or_init(|| {
+        let cwd = env::current_dir().unwrap_or_default();
+        let manifest_dir = env::var_os("CARGO_MANIFEST_DIR")
+            .map_or_else(|| PathBuf::from(env!("CARGO_MANIFEST_DIR")), PathBuf::from);
+
+        let root = resolve_data_root(env::var_os(DATA_DIR_ENV), &cwd, &manifest_dir)
+            .unwrap_or_else(|e| {
+                eprintln!("Warning: {e}");
+                manifest_dir.join("data")
+            });
+
+        match root.strip_prefix(&cwd) {
+            Ok(relative) if !relative.as_os_str().is_empty() => relative.to_path_buf(),
+            _ => root,
+        }
+    })
+}
+
+fn resolve_data_root(
+    from_env: Option<OsString>,
+    cwd: &Path,
+    manifest_dir: &Path,
+) -> Result<PathBuf, Error> {
+    if let Some(dir) = from_env.filter(|dir| !dir.is_empty()) {
+        return Ok(cwd.join(dir));
+    }
+
+    for dir in cwd.ancestors() {
+        let path = dir.join(CONFIG_FILE_NAME);
+        if !path.is_file() {
+            continue;
+        }
+
+        let content = fs::read_to_string(&path).map_err(|e| Error::Read(path.clone(), e))?;
+        let config: toml::Table = content
+            .parse()
+            .map_err(|e| Error::Config(path.clone(), format!("{e}")))?;
+
+        match config.get("data_dir") {
+            Some(toml::Value::String(data_dir)) => return Ok(dir.join(data_dir)),
+            Some(_) => {
+                return Err(Error::Config(path, "`data_dir` must be a string.".into()));
+            }
+            None => break,
+        }
+    }
+
+    Ok(manifest_dir.join("data"))
+}
+
+/// Path of a data file of a puzzle, e.g. `data/2024/inputs/01.txt` or `data/2024/examples/01-2.txt`.
+pub fn path(folder: &str, puzzle: PuzzleId, part: Option<u8>) -> PathBuf {
+    let file_name = match part {
+        Some(part) => format!("{}-{part}.txt", puzzle.day),
+        None => format!("{}.txt", puzzle.day),
+    };
+    data_dir(puzzle.year).join(folder).join(file_name)
+}
+
+pub fn read_path(path: &Path) -> Result<String, Error> {
+    fs::read_to_string(path).map_err(|e| Error::Read(path.to_path_buf(), e))
+}
+
+pub fn read_stdin() -> Result<String, Error> {
+    let mut input = String::new();
+    io::stdin()
+        .read_to_string(&mut input)
+        .map_err(Error::Stdin)?;
+    Ok(input)
+}
+
+/// Where a solution reads its input from.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum Source {
+    /// The real input of the puzzle, e.g. `data/2024/inputs/01.txt`.
+    #[default]
+    Puzzle,
+    /// An example of the puzzle: `data/2024/examples/01.txt`, or `01-<n>.txt` if a number is given.
+    Example(Option<u8>),
+    File(PathBuf),
+    Stdin,
+}
+
+impl Source {
+    /// Read the source from `--input <path>` (`-` for stdin) or `--example [<n>]`.
+    pub fn from_args() -> Self {
+        let args: Vec<String> = env::args().collect();
+        Self::parse_args(&args).unwrap_or_else(|| {
+            eprintln!("Unexpected command-line input. Format: cargo solve 1 [--input <path> | --example [<n>]]");
+            std::process::exit(1);
+        })
+    }
+
+    fn parse_args(args: &[String]) -> Option<Self> {
+        let value = |flag: &str| {
+            let index = args.iter().position(|x| x == flag)? + 1;
+            Some(args.get(index).map(String::as_str))
+        };
+
+        match (value("--input"), value("--example")) {
+            (Some(Some("-")), None) => Some(Source::Stdin),
+            (Some(Some(path)), None) => Some(Source::File(path.into())),
+            // The number is optional, so a following flag belongs to something else.
+            (None, Some(Some(n))) if !n.starts_with("--") => {
+                n.parse().ok().map(|n| Source::Example(Some(n)))
+            }
+            (None, Some(_)) => Some(Source::Example(None)),
+            (None, None) => Some(Source::Puzzle),
+            _ => None,
+        }
+    }
+
+    /// Arguments that reproduce this source when passed to a solution binary.
+    pub fn to_args(&self) -> Vec<String> {
+        match self {
+            Source::Puzzle => vec![],
+            Source::Example(None) => vec!["--example".into()],
+            Source::Example(Some(n)) => vec!["--example".into(), n.to_string()],
+            Source::File(path) => vec!["--input".into(), path.display().to_string()],
+            Source::Stdin => vec!["--input".into(), "-".into()],
+        }
+    }
+
+    pub fn read(&self, puzzle: PuzzleId) -> Result<String, Error> {
+        match self {
+            Source::Puzzle => read_path(&path("inputs", puzzle, None)),
+            Source::Example(n) => read_path(&path("examples", puzzle, *n)),
+            Source::File(path) => read_path(path),
+            Source::Stdin => read_stdin(),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{resolve_data_root, Error, Source};
+    use std::{env, fs, path::Path};
+
+    #[test]
+    fn resolves_data_root() {
+        let dir = env::temp_dir().join(format!("aoc-input-{}", std::process::id()));
+        let nested = dir.join("a").join("b");
+        fs::create_dir_all(&nested).unwrap();
+        let manifest_dir = Path::new("/crate");
+
+        // without a config file, the data directory of the crate is used.
+        assert_eq!(
+            resolve_data_root(None, &nested, manifest_dir).unwrap(),
+            manifest_dir.join("data")
+        );
+
+        // config files are found in parent directories.
+        fs::write(dir.join("aoc.toml"), "data_dir = \"puzzles\"").unwrap();
+        assert_eq!(
+            resolve_data_root(None, &nested, manifest_dir).unwrap(),
+            dir.join("puzzles")
+        );
+
+        // the environment takes precedence.
+        assert_eq!(
+            resolve_data_root(Some("/elsewhere".into()), &nested, manifest_dir).unwrap(),
+            Path::new("/elsewhere")
+        );
+
+        fs::write(dir.join("aoc.toml"), "data_dir = 1").unwrap();
+        assert!(matches!(
+            resolve_data_root(None, &nested, manifest_dir),
+            Err(Error::Config(..))
+        ));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn roundtrips_args() {
+        for source in [
+            Source::Puzzle,
+            Source::Example(None),
+            Source::Example(Some(2)),
+            Source::File("other.txt".into()),
+            Source::Stdin,
+        ] {
+            assert_eq!(Source::parse_args(&source.to_args()), Some(source));
+        }
+    }
+
+    #[test]
+    fn rejects_conflicting_args() {
+        let args = ["--input", "a.txt", "--example"].map(String::from);
+        assert_eq!(Source::parse_args(&args), None);
+        assert_eq!(Source::parse_args(&["--input".into()]), None);
+
+        let args = ["--example", "foo"].map(String::from);
+        assert_eq!(Source::parse_args(&args), None);
+        let args = ["--example", "--explain"].map(String::from);
+        assert_eq!(Source::parse_args(&args), Some(Source::Example(None)));
+    }
+
+    #[test]
+    fn reports_attempted_path() {
+        let err = super::read_path(Path::new("does/not/exist.txt")).unwrap_err();
+        assert!(err.to_string().contains("\"does/not/exist.txt\""));
+    }
+}
@@ -0,0 +1,352 @@
+/// Local record of every answer submitted to the Advent of Code website.
+/// Used to refuse answers that are already known to be wrong before they reach the server.
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs,
+    io::{Error, ErrorKind},
+    path::Path,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::{aoc_client::Verdict, data_dir, Day, Year};
+
+static LEDGER_FILE_NAME: &str = "ledger.json";
+
+/// A single submitted answer and the server's verdict.
+#[derive(Clone, Debug)]
+pub struct Attempt {
+    pub day: Day,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+}
+
+/// All submitted answers.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Ledger {
+    pub data: Vec<Attempt>,
+}
+
+/// Reason for refusing to submit an answer.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Rejection {
+    /// The part was already solved with this answer.
+    AlreadyCorrect,
+    /// The part was already solved with a different answer.
+    AlreadySolved(String),
+    /// The exact answer was submitted before and was wrong.
+    KnownWrong,
+    /// A smaller or equal answer was reported as too high.
+    AboveBound(String),
+    /// A larger or equal answer was reported as too low.
+    BelowBound(String),
+}
+
+impl Display for Rejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Rejection::AlreadyCorrect => write!(f, "this answer was already accepted."),
+            Rejection::AlreadySolved(answer) => {
+                write!(f, "this part was already solved with `{answer}`.")
+            }
+            Rejection::KnownWrong => write!(f, "this answer was already rejected."),
+            Rejection::AboveBound(answer) => {
+                write!(f, "`{answer}` was already too high.")
+            }
+            Rejection::BelowBound(answer) => {
+                write!(f, "`{answer}` was already too low.")
+            }
+        }
+    }
+}
+
+impl Ledger {
+    /// Dehydrate the ledger to `data/<year>/ledger.json`.
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(data_dir(year).join(LEDGER_FILE_NAME))?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate the ledger from a JSON file. If not present, returns an empty ledger.
+    /// A file that can not be read or parsed is an error, so that it is never overwritten.
+    pub fn read_from_file(year: Year) -> Result<Self, String> {
+        Self::read_from_path(&data_dir(year).join(LEDGER_FILE_NAME))
+    }
+
+    fn read_from_path(path: &Path) -> Result<Self, String> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Ledger::default()),
+            Err(e) => return Err(format!("\"{}\": {e}", path.display())),
+        };
+        Ledger::try_from(content).map_err(|e| format!("\"{}\": {e}", path.display()))
+    }
+
+    /// Append an attempt, stamped with the current time.
+    pub fn record(&mut self, day: Day, part: u8, answer: &str, verdict: Verdict) {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+
+        self.data.push(Attempt {
+            day,
+            part,
+            answer: answer.to_string(),
+            verdict,
+            timestamp,
+        });
+    }
+
+    /// Check whether an answer is worth submitting given what the server told us before.
+    pub fn check(&self, day: Day, part: u8, answer: &str) -> Result<(), Rejection> {
+        let attempts: Vec<&Attempt> = self
+            .data
+            .iter()
+            .filter(|a| a.day == day && a.part == part)
+            .collect();
+
+        if let Some(correct) = attempts.iter().find(|a| a.verdict == Verdict::Correct) {
+            return Err(if correct.answer == answer {
+                Rejection::AlreadyCorrect
+            } else {
+                Rejection::AlreadySolved(correct.answer.clone())
+            });
+        }
+
+        if attempts
+            .iter()
+            .any(|a| a.answer == answer && a.verdict.is_wrong())
+        {
+            return Err(Rejection::KnownWrong);
+        }
+
+        let Ok(value) = answer.parse::<i128>() else {
+            return Ok(());
+        };
+
+        let bound = |verdict: Verdict| {
+            attempts
+                .iter()
+                .filter(move |a| a.verdict == verdict)
+                .filter_map(|a| Some((a.answer.parse::<i128>().ok()?, &a.answer)))
+        };
+
+        if let Some((_, answer)) = bound(Verdict::TooHigh)
+            .filter(|(high, _)| value >= *high)
+            .min_by_key(|(high, _)| *high)
+        {
+            return Err(Rejection::AboveBound(answer.clone()));
+        }
+
+        if let Some((_, answer)) = bound(Verdict::TooLow)
+            .filter(|(low, _)| value <= *low)
+            .max_by_key(|(low, _)| *low)
+        {
+            return Err(Rejection::BelowBound(answer.clone()));
+        }
+
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Ledger> for JsonValue {
+    fn from(value: Ledger) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Ledger {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Ledger {
+            data: json_data
+                .iter()
+                .map(Attempt::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Attempt> for JsonValue {
+    fn from(value: &Attempt) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert(
+            "verdict".into(),
+            JsonValue::String(value.verdict.to_string()),
+        );
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Attempt {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected attempt to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected attempt.day to be a Day struct.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .map(|p| *p as u8)
+            .filter(|p| *p == 1 || *p == 2)
+            .ok_or("Expected attempt.part to be 1 or 2.")?;
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected attempt.answer to be a string.")?;
+
+        let verdict = json
+            .get("verdict")
+            .and_then(|v| v.get::<String>())
+            .and_then(|v| Verdict::from_str(v).ok())
+            .ok_or("Expected attempt.verdict to be a verdict.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .map(|t| *t as u64)
+            .ok_or("Expected attempt.timestamp to be a number.")?;
+
+        Ok(Attempt {
+            day,
+            part,
+            answer: answer.clone(),
+            verdict,
+            timestamp,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Ledger, Rejection};
+    use crate::{day, template::aoc_client::Verdict};
+    use std::{env, fs};
+
+    fn get_mock_ledger() -> Ledger {
+        let mut ledger = Ledger::default();
+        ledger.record(day!(1), 1, "100", Verdict::TooHigh);
+        ledger.record(day!(1), 1, "50", Verdict::TooLow);
+        ledger.record(day!(1), 1, "75", Verdict::Incorrect);
+        ledger.record(day!(1), 1, "80", Verdict::RateLimited);
+        ledger.record(day!(2), 1, "42", Verdict::Correct);
+        ledger
+    }
+
+    #[test]
+    fn refuses_known_wrong_answers() {
+        let ledger = get_mock_ledger();
+        assert_eq!(ledger.check(day!(1), 1, "75"), Err(Rejection::KnownWrong));
+    }
+
+    #[test]
+    fn refuses_answers_outside_bounds() {
+        let ledger = get_mock_ledger();
+        assert_eq!(
+            ledger.check(day!(1), 1, "120"),
+            Err(Rejection::AboveBound("100".into()))
+        );
+        assert_eq!(
+            ledger.check(day!(1), 1, "40"),
+            Err(Rejection::BelowBound("50".into()))
+        );
+    }
+
+    #[test]
+    fn allows_answers_within_bounds() {
+        let ledger = get_mock_ledger();
+        assert_eq!(ledger.check(day!(1), 1, "80"), Ok(()));
+        assert_eq!(ledger.check(day!(1), 1, "99"), Ok(()));
+        assert_eq!(ledger.check(day!(1), 2, "120"), Ok(()));
+    }
+
+    #[test]
+    fn refuses_solved_parts() {
+        let ledger = get_mock_ledger();
+        assert_eq!(
+            ledger.check(day!(2), 1, "42"),
+            Err(Rejection::AlreadyCorrect)
+        );
+        assert_eq!(
+            ledger.check(day!(2), 1, "43"),
+            Err(Rejection::AlreadySolved("42".into()))
+        );
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let ledger = get_mock_ledger();
+        let json = tinyjson::JsonValue::from(ledger.clone())
+            .stringify()
+            .unwrap();
+        let parsed = Ledger::try_from(json).unwrap();
+        assert_eq!(parsed.data.len(), ledger.data.len());
+        assert_eq!(parsed.data[0].answer, "100");
+        assert_eq!(parsed.data[0].verdict, Verdict::TooHigh);
+        assert_eq!(parsed.data[4].day, day!(2));
+    }
+
+    #[test]
+    fn refuses_corrupt_ledgers() {
+        let path = env::temp_dir().join(format!("aoc-ledger-{}.json", std::process::id()));
+        assert_eq!(Ledger::read_from_path(&path).unwrap().data.len(), 0);
+
+        fs::write(&path, "{\"data\": [").unwrap();
+        let err = Ledger::read_from_path(&path).unwrap_err();
+        assert!(err.contains("not valid JSON"));
+
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -1,68 +1,125 @@
-use std::{env, fs};
+use std::path::PathBuf;
 
-pub mod aoc_cli;
+pub mod answers;
+pub mod aoc_client;
 pub mod commands;
+pub mod examples;
+pub mod input;
+pub mod registry;
 pub mod runner;
 
 pub use day::*;
+pub use year::*;
 
+mod build;
+mod compare;
 mod day;
+mod history;
+mod ledger;
 mod readme_benchmarks;
 mod run_multi;
+mod stats;
 mod timings;
+mod year;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
+pub const ANSI_RED: &str = "\x1b[31m";
+pub const ANSI_GREEN: &str = "\x1b[32m";
 
-/// Helper function that reads a text file to a string.
+/// The data directory of a year, e.g. `data/2024`. See [`input::data_root`] for how `data` is located.
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+pub fn data_dir(year: Year) -> PathBuf {
+    input::data_root().join(year.to_string())
+}
+
+/// Helper function that reads a text file to a string, e.g. `data/2024/inputs/01.txt`.
+/// The error names the path that was tried.
+pub fn read_file(folder: &str, puzzle: PuzzleId) -> Result<String, input::Error> {
+    input::read_path(&input::path(folder, puzzle, None))
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
-#[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+pub fn read_file_part(folder: &str, puzzle: PuzzleId, part: u8) -> Result<String, input::Error> {
+    input::read_path(&input::path(folder, puzzle, Some(part)))
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Creates the constant `PUZZLE` and sets up the input and runner for each part.
+/// Also creates the static `SOLUTION`, which registers the parts with the `all-in-one` runner.
 ///
-/// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+/// The first two parameters are the year and the day of the puzzle, e.g. `solution!(2024, 1)`.
+/// The optional, third parameter (1 or 2) allows you to only run a single part of the solution.
+/// A last parameter `explain = <function>`, e.g. `solution!(2024, 6, explain = explain)` or
+/// `solution!(2024, 6, 1, explain = explain)`, registers a function that describes how the
+/// answers come about, printed instead of the answers when running with `--explain`. The
+/// function takes the input and returns a `Result<String, E>` where `E` implements `Display`.
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+    ($year:expr, $day:expr) => {
+        $crate::solution!(@impl $year, $day, $crate::template::runner::no_explanation, [part_one, 1] [part_two, 2]);
+    };
+    ($year:expr, $day:expr, 1) => {
+        $crate::solution!(@impl $year, $day, $crate::template::runner::no_explanation, [part_one, 1]);
+    };
+    ($year:expr, $day:expr, 2) => {
+        $crate::solution!(@impl $year, $day, $crate::template::runner::no_explanation, [part_two, 2]);
     };
-    ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+    ($year:expr, $day:expr, explain = $explain:expr) => {
+        $crate::solution!(@impl $year, $day, $explain, [part_one, 1] [part_two, 2]);
     };
-    ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+    ($year:expr, $day:expr, 1, explain = $explain:expr) => {
+        $crate::solution!(@impl $year, $day, $explain, [part_one, 1]);
     };
+    ($year:expr, $day:expr, 2, explain = $explain:expr) => {
+        $crate::solution!(@impl $year, $day, $explain, [part_two, 2]);
+    };
+
+    (@impl $year:expr, $day:expr, $explain:expr, $( [$func:expr, $part:expr] )*) => {
+        /// The current puzzle.
+        const PUZZLE: $crate::template::PuzzleId =
+            $crate::template::PuzzleId::new($crate::year!($year), $crate::day!($day));
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
-        /// The current day.
-        const DAY: $crate::template::Day = $crate::day!($day);
+        /// The parts of the current day, see [`advent_of_code::template::registry`].
+        #[allow(dead_code)]
+        pub static SOLUTION: $crate::template::registry::Solution =
+            $crate::template::registry::Solution {
+                puzzle: PUZZLE,
+                parts: &[$(
+                    |input, bench| $crate::template::runner::measure_part($func, input, $part, bench),
+                )*],
+            };
 
-        #[cfg(feature = "dhat-heap")]
+        // the `all-in-one` runner links all days, so it can not install an allocator per day.
+        #[cfg(all(feature = "dhat-heap", not(feature = "all-in-one")))]
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
+        #[allow(dead_code)]
         fn main() {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+
+            if std::env::args().any(|x| x == "--verify") {
+                return $crate::template::examples::run_verify(&SOLUTION);
+            }
+
+            #[cfg(not(feature = "visualize"))]
+            if $crate::visualize::Output::from_args(&std::env::args().collect::<Vec<_>>()).is_some() {
+                eprintln!("Visualization is not compiled in, build with `--features visualize`.");
+            }
+
+            let input = match $crate::template::input::Source::from_args().read(PUZZLE) {
+                Ok(input) => input,
+                Err(e) => {
+                    eprintln!("{e}");
+                    std::process::exit(1);
+                }
+            };
+            if std::env::args().any(|x| x == "--explain") {
+                return run_explain($explain, &input);
+            }
+            $( run_part($func, &input, PUZZLE, $part); )*
+            $crate::visualize::finish();
         }
     };
 }
@@ -0,0 +1,37 @@
+/// Registry of solutions linked into the runner binary with the `all-in-one` feature.
+/// Each day registers its parts via the `solution!` macro, which lets `all` and `time` run them
+/// in-process instead of spawning the per-day binaries.
+use std::sync::OnceLock;
+
+use crate::template::runner::{BenchConfig, PartResult};
+use crate::template::PuzzleId;
+
+/// Runs one part on the given input. Benchmarks it if a config is passed.
+pub type PartRunner = fn(&str, Option<&BenchConfig>) -> PartResult;
+
+/// The parts of one puzzle, as generated by the `solution!` macro.
+pub struct Solution {
+    pub puzzle: PuzzleId,
+    pub parts: &'static [PartRunner],
+}
+
+static SOLUTIONS: OnceLock<&'static [&'static Solution]> = OnceLock::new();
+
+/// Make the given solutions available to [`get`]. Only the first call has an effect.
+pub fn register(solutions: &'static [&'static Solution]) {
+    let _ = SOLUTIONS.set(solutions);
+}
+
+/// Whether solutions have been registered, i.e. whether days can be run in-process.
+pub fn is_registered() -> bool {
+    SOLUTIONS.get().is_some()
+}
+
+/// The registered solution of a puzzle, if any.
+pub fn get(puzzle: PuzzleId) -> Option<&'static Solution> {
+    SOLUTIONS
+        .get()?
+        .iter()
+        .find(|solution| solution.puzzle == puzzle)
+        .copied()
+}
@@ -1,41 +1,171 @@
-use std::{collections::HashSet, io};
+use std::{
+    collections::{BTreeMap, HashSet},
+    io,
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc, Mutex,
+    },
+    thread,
+};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{
+    runner::BenchConfig, Day, PuzzleId, Year, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
 
 use super::{
     all_days,
+    build::{self, Bins, Profile},
+    input::Source,
+    registry,
+    runner::{format_result, format_stats, Answer, PartResult},
+    stats::Stats,
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
-    let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+/// A line of output of a solution bin.
+#[derive(Clone, Debug)]
+pub enum Line {
+    Out(String),
+    Err(String),
+}
 
-    let mut need_space = false;
+impl Line {
+    fn print(&self) {
+        match self {
+            Line::Out(line) => println!("{line}"),
+            Line::Err(line) => eprintln!("{line}"),
+        }
+    }
+}
 
-    // NOTE: use non-duplicate, sorted day values.
-    all_days()
-        .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
+/// How the solution of a day is executed.
+enum Runner {
+    /// Spawn the compiled solution bin.
+    Bins(Bins),
+    /// Call the solution in this process, see [`registry`].
+    InProcess,
+}
+
+impl Runner {
+    /// Prefer running in-process, otherwise build the solution bins with the given profile.
+    fn new(is_release: bool) -> Option<Self> {
+        if registry::is_registered() {
+            return Some(Runner::InProcess);
+        }
+
+        let profile = if is_release {
+            Profile::Release
+        } else {
+            Profile::Debug
+        };
+
+        match build::build_bins(profile, &[], true) {
+            Ok(bins) => Some(Runner::Bins(bins)),
+            Err(_) => {
+                eprintln!("Failed to build solutions.");
+                None
             }
-            need_space = true;
+        }
+    }
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+    fn run(
+        &self,
+        puzzle: PuzzleId,
+        input: &Source,
+        bench: Option<&BenchConfig>,
+        emit: &(dyn Fn(&Line) + Sync),
+    ) -> Vec<PartResult> {
+        match self {
+            Runner::Bins(bins) => {
+                match child_commands::run_solution(bins.get(puzzle), input, bench, emit) {
+                    Ok(results) => results,
+                    // e.g. the bin could not be spawned, this should not take down the other days.
+                    Err(e) => [1, 2]
+                        .map(|part| {
+                            let result = PartResult {
+                                part,
+                                answer: Answer::Failed(e.to_string()),
+                                stats: Stats::default(),
+                            };
+                            emit(&Line::Out(format_result(
+                                &result.answer,
+                                &format!("Part {part}"),
+                                "",
+                            )));
+                            result
+                        })
+                        .into(),
+                }
+            }
+            Runner::InProcess => {
+                let Some(solution) = registry::get(puzzle) else {
+                    return vec![];
+                };
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+                let input = match input.read(puzzle) {
+                    Ok(input) => input,
+                    Err(e) => {
+                        emit(&Line::Err(e.to_string()));
+                        return vec![];
+                    }
+                };
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
+                // a panicking solution should not take down the other days.
+                panic::catch_unwind(AssertUnwindSafe(|| {
+                    solution
+                        .parts
+                        .iter()
+                        .map(|run_part| {
+                            let result = run_part(&input, bench);
+                            emit(&Line::Out(format_result(
+                                &result.answer,
+                                &format!("Part {}", result.part),
+                                &format_stats(&result.stats),
+                            )));
+                            result
+                        })
+                        .collect()
+                }))
+                .unwrap_or_default()
             }
-        });
+        }
+    }
+}
+
+/// Run the given days of a year on `input`. If `bench` is set, each part is benchmarked and timings are returned.
+/// With `jobs > 1`, up to `jobs` days run concurrently and their output is printed in day order.
+/// Benchmarks always run serially so that solutions do not compete for the CPU.
+///
+/// If the runner was built with the `all-in-one` feature, days run in-process and `is_release`
+/// is ignored in favor of the profile of the runner itself.
+pub fn run_multi(
+    year: Year,
+    days_to_run: &HashSet<Day>,
+    is_release: bool,
+    input: &Source,
+    bench: Option<&BenchConfig>,
+    jobs: usize,
+) -> Option<Timings> {
+    // NOTE: use non-duplicate, sorted day values.
+    let puzzles: Vec<PuzzleId> = all_days()
+        .filter(|day| days_to_run.contains(day))
+        .map(|day| PuzzleId::new(year, day))
+        .collect();
+
+    let runner = Runner::new(is_release)?;
 
-    if is_timed {
-        let timings = Timings { data: timings };
+    let timings = if jobs > 1 && bench.is_none() {
+        run_parallel(&puzzles, &runner, input, jobs)
+    } else {
+        run_serial(&puzzles, &runner, input, bench)
+    };
+
+    if bench.is_some() {
+        let timings = Timings {
+            data: timings,
+            history: vec![],
+        };
         let total_millis = timings.total_millis();
         println!(
             "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
@@ -46,29 +176,149 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
     }
 }
 
-#[allow(dead_code)]
+/// Run each puzzle once on its real input and pass its part results to `f` instead of printing them.
+/// Only stderr of the solutions is forwarded. Returns `None` if the solutions could not be built.
+pub fn run_quiet(
+    puzzles: &[PuzzleId],
+    is_release: bool,
+    mut f: impl FnMut(PuzzleId, Vec<PartResult>),
+) -> Option<()> {
+    let runner = Runner::new(is_release)?;
+
+    let emit = |line: &Line| {
+        if let Line::Err(_) = line {
+            line.print();
+        }
+    };
+
+    for puzzle in puzzles {
+        f(*puzzle, runner.run(*puzzle, &Source::Puzzle, None, &emit));
+    }
+
+    Some(())
+}
+
+fn run_serial(
+    puzzles: &[PuzzleId],
+    runner: &Runner,
+    input: &Source,
+    bench: Option<&BenchConfig>,
+) -> Vec<Timing> {
+    let mut timings: Vec<Timing> = Vec::with_capacity(puzzles.len());
+
+    for (i, puzzle) in puzzles.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        print_header(puzzle.day);
+
+        let results = runner.run(*puzzle, input, bench, &Line::print);
+
+        if results.is_empty() {
+            println!("Not solved.");
+        } else {
+            timings.push(child_commands::collect_timing(&results, puzzle.day));
+        }
+    }
+
+    timings
+}
+
+/// Run up to `jobs` days at a time.
+/// Output of each day is buffered and printed as soon as all previous days are done.
+fn run_parallel(puzzles: &[PuzzleId], runner: &Runner, input: &Source, jobs: usize) -> Vec<Timing> {
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel::<(usize, Vec<Line>, Vec<PartResult>)>();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(puzzles.len()) {
+            let sender = sender.clone();
+            let next = &next;
+
+            scope.spawn(move || loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let Some(puzzle) = puzzles.get(i) else {
+                    break;
+                };
+
+                let buffer = Mutex::new(vec![]);
+                let emit = |line: &Line| buffer.lock().unwrap().push(line.clone());
+                let results = runner.run(*puzzle, input, None, &emit);
+
+                if sender
+                    .send((i, buffer.into_inner().unwrap(), results))
+                    .is_err()
+                {
+                    break;
+                }
+            });
+        }
+        drop(sender);
+
+        let mut timings = Vec::with_capacity(puzzles.len());
+        let mut pending = BTreeMap::new();
+        let mut printed = 0;
+
+        for (i, lines, results) in receiver {
+            pending.insert(i, (lines, results));
+
+            while let Some((lines, results)) = pending.remove(&printed) {
+                let day = puzzles[printed].day;
+                if printed > 0 {
+                    println!();
+                }
+                print_header(day);
+                lines.iter().for_each(Line::print);
+
+                if results.is_empty() {
+                    println!("Not solved.");
+                } else {
+                    timings.push(child_commands::collect_timing(&results, day));
+                }
+
+                printed += 1;
+            }
+        }
+
+        timings
+    })
+}
+
+fn print_header(day: Day) {
+    println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+    println!("------");
+}
+
 #[derive(Debug)]
 pub enum Error {
     BrokenPipe,
     IO(io::Error),
 }
 
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::BrokenPipe => write!(f, "could not read the output of the solution"),
+            Error::IO(e) => write!(f, "could not run the solution: {e}"),
+        }
+    }
+}
+
 impl From<std::io::Error> for Error {
     fn from(e: std::io::Error) -> Self {
         Error::IO(e)
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
 /// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// This module encapsulates interaction with these binaries, both invoking them as well as collecting their results.
 pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use super::{Error, Line};
+    use crate::template::{
+        input::Source,
+        runner::{format_result, format_stats, Answer, BenchConfig, PartResult},
+        Day,
+    };
     use std::{
         io::{BufRead, BufReader},
         path::Path,
@@ -76,30 +326,36 @@ pub mod child_commands {
         thread,
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
+    /// Run the compiled solution bin of a day in `--json` mode.
+    /// Part results are passed to `emit` in human-readable form, any other output is forwarded unchanged.
+    pub fn run_solution(
+        bin: Option<&Path>,
+        input: &Source,
+        bench: Option<&BenchConfig>,
+        emit: &(dyn Fn(&Line) + Sync),
+    ) -> Result<Vec<PartResult>, Error> {
+        // skip invocation for days that have not been scaffolded yet.
+        let Some(bin) = bin else {
             return Ok(vec![]);
-        }
+        };
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        let mut args = vec!["--json"];
 
-        if is_release {
-            args.push("--release");
-        }
+        let input_args = input.to_args();
+        args.extend(input_args.iter().map(String::as_str));
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
+        let bench_args = bench.map(BenchConfig::to_args).unwrap_or_default();
+
+        if bench.is_some() {
+            // mirror `--time` flag and bench config to child invocations.
             args.push("--time");
+            args.extend(bench_args.iter().map(String::as_str));
         }
 
         // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        // forward output to stdout/stderr while grabbing part results.
 
-        let mut cmd = Command::new("cargo")
+        let mut cmd = Command::new(bin)
             .args(&args)
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
@@ -108,85 +364,75 @@ pub mod child_commands {
         let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
         let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
 
-        let mut output = vec![];
+        let mut results = vec![];
 
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
+        thread::scope(|scope| {
+            scope.spawn(|| {
+                stderr.lines().for_each(|line| {
+                    emit(&Line::Err(line.unwrap()));
+                });
             });
-        });
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
-        }
+            for line in stdout.lines() {
+                let line = line.unwrap();
+                match line.parse::<PartResult>() {
+                    Ok(result) => {
+                        emit(&Line::Out(format_result(
+                            &result.answer,
+                            &format!("Part {}", result.part),
+                            &format_stats(&result.stats),
+                        )));
+                        results.push(result);
+                    }
+                    Err(_) => emit(&Line::Out(line)),
+                }
+            }
+        });
 
-        thread.join().unwrap();
         cmd.wait()?;
 
-        Ok(output)
+        Ok(results)
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
+    /// Collect the timings of all successful parts of a day. Failed parts are recorded with their error.
+    pub fn collect_timing(results: &[PartResult], day: Day) -> super::Timing {
         let mut timings = super::Timing {
             day,
             part_1: None,
             part_2: None,
+            part_1_stats: None,
+            part_2_stats: None,
+            part_1_error: None,
+            part_2_error: None,
             total_nanos: 0_f64,
         };
 
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
+        for result in results {
+            if let Answer::Failed(error) = &result.answer {
+                match result.part {
+                    1 => timings.part_1_error = Some(error.clone()),
+                    2 => timings.part_2_error = Some(error.clone()),
+                    _ => {}
                 }
+            }
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
-
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
-                }
+            if !result.is_success() {
+                continue;
+            }
 
-                timings.total_nanos += nanos;
-            });
+            let timing_str = format!("{:.1?}", result.stats.mean_duration());
+            let stats = Some(result.stats.clone());
 
-        timings
-    }
+            match result.part {
+                1 => (timings.part_1, timings.part_1_stats) = (Some(timing_str), stats),
+                2 => (timings.part_2, timings.part_2_stats) = (Some(timing_str), stats),
+                _ => continue,
+            }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
+            timings.total_nanos += result.stats.mean;
+        }
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
+        timings
     }
 
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
@@ -205,53 +451,73 @@ pub mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::collect_timing;
 
-        use crate::day;
+        use crate::{
+            day,
+            template::{
+                runner::{Answer, PartResult},
+                stats::Stats,
+            },
+        };
+
+        fn part(part: u8, answer: Option<&str>, nanos: f64) -> PartResult {
+            PartResult {
+                part,
+                answer: answer.map_or(Answer::Unsolved, |a| Answer::Solved(a.into())),
+                stats: Stats {
+                    mean: nanos,
+                    samples: 100,
+                    ..Stats::default()
+                },
+            }
+        }
 
         #[test]
-        fn parses_execution_times() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
-                ],
+        fn collects_execution_times() {
+            let res = collect_timing(
+                &[part(1, Some("0"), 74.13), part(2, Some("10"), 74_130_000.0)],
                 day!(1),
             );
             assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_eq!(res.part_1.unwrap(), "74.0ns");
+            assert_eq!(res.part_2.unwrap(), "74.1ms");
         }
 
         #[test]
-        fn parses_with_patterns_in_input() {
-            let res = parse_exec_time(
+        fn collects_with_patterns_in_answer() {
+            let res = collect_timing(
                 &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
+                    part(1, Some("@ @ @ ( ) ms (2s @ 5 samples)"), 2e9),
+                    part(2, Some("10s"), 1e8),
                 ],
                 day!(1),
             );
             assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+            assert_eq!(res.part_1.unwrap(), "2.0s");
+            assert_eq!(res.part_2.unwrap(), "100.0ms");
         }
 
         #[test]
-        fn parses_missing_parts() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
+        fn collects_missing_parts() {
+            let res = collect_timing(&[part(1, None, 10.0), part(2, None, 10.0)], day!(1));
             assert_approx_eq!(res.total_nanos, 0_f64);
             assert_eq!(res.part_1.is_none(), true);
             assert_eq!(res.part_2.is_none(), true);
         }
+
+        #[test]
+        fn collects_failed_parts() {
+            let failed = PartResult {
+                part: 2,
+                answer: Answer::Failed("invalid input".into()),
+                stats: Stats::default(),
+            };
+            let res = collect_timing(&[part(1, Some("1"), 10.0), failed], day!(1));
+            assert_approx_eq!(res.total_nanos, 10_f64);
+            assert_eq!(res.part_1_error, None);
+            assert_eq!(res.part_2, None);
+            assert_eq!(res.part_2_error.unwrap(), "invalid input");
+        }
     }
 }
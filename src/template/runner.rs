@@ -1,35 +1,221 @@
 /// Encapsulates code that interacts with solution functions.
+use std::collections::HashMap;
 use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{stdout, Write};
-use std::process::Output;
+use std::str::FromStr;
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
-use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use tinyjson::JsonValue;
 
-pub fn run_part<I: Copy, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+use crate::template::input::Source;
+use crate::template::ledger::Ledger;
+use crate::template::stats::Stats;
+use crate::template::{answers, aoc_client, PuzzleId, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{ANSI_BOLD, ANSI_RED};
+
+/// What running a part produced.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Answer {
+    Solved(String),
+    /// The part returned `None`, e.g. because it is not implemented yet.
+    Unsolved,
+    /// The part returned an error.
+    Failed(String),
+}
+
+impl Answer {
+    pub fn solved(&self) -> Option<&str> {
+        match self {
+            Answer::Solved(answer) => Some(answer),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Solved(answer) => write!(f, "{answer}"),
+            Answer::Unsolved => write!(f, "nothing"),
+            Answer::Failed(error) => write!(f, "error: {error}"),
+        }
+    }
+}
+
+/// Return types of solution parts: `Option<T>` and `Result<T, E>`, where `T` and `E` implement `Display`.
+pub trait ToAnswer {
+    fn to_answer(&self) -> Answer;
+}
+
+impl<T: Display> ToAnswer for Option<T> {
+    fn to_answer(&self) -> Answer {
+        match self {
+            Some(answer) => Answer::Solved(answer.to_string()),
+            None => Answer::Unsolved,
+        }
+    }
+}
+
+impl<T: Display, E: Display> ToAnswer for Result<T, E> {
+    fn to_answer(&self) -> Answer {
+        match self {
+            Ok(answer) => Answer::Solved(answer.to_string()),
+            Err(error) => Answer::Failed(error.to_string()),
+        }
+    }
+}
+
+/// The machine-readable result of running one part, printed as a single JSON line in `--json` mode.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartResult {
+    pub part: u8,
+    pub answer: Answer,
+    pub stats: Stats,
+}
+
+impl PartResult {
+    pub fn is_success(&self) -> bool {
+        matches!(self.answer, Answer::Solved(_))
+    }
+}
+
+/// Settings for benchmarking a solution with `--time`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BenchConfig {
+    /// Approximate total time spent on measured iterations.
+    pub budget: Duration,
+    /// Time spent running the solution before measuring.
+    pub warmup: Duration,
+    pub min_samples: u128,
+    pub max_samples: u128,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self {
+            budget: Duration::from_secs(1),
+            warmup: Duration::from_millis(100),
+            min_samples: 10,
+            max_samples: 10000,
+        }
+    }
+}
+
+impl BenchConfig {
+    /// Read the config from `--budget <ms>`, `--warmup <ms>`, `--min-samples <n>` and `--max-samples <n>`.
+    pub fn from_args() -> Self {
+        let args: Vec<String> = env::args().collect();
+        let value = |flag: &str| {
+            let index = args.iter().position(|x| x == flag)? + 1;
+            args.get(index)?.parse::<u64>().ok()
+        };
+
+        let default = Self::default();
+        Self {
+            budget: value("--budget").map_or(default.budget, Duration::from_millis),
+            warmup: value("--warmup").map_or(default.warmup, Duration::from_millis),
+            min_samples: value("--min-samples").map_or(default.min_samples, u128::from),
+            max_samples: value("--max-samples").map_or(default.max_samples, u128::from),
+        }
+    }
+
+    /// Arguments that reproduce this config when passed to a solution binary.
+    pub fn to_args(&self) -> Vec<String> {
+        vec![
+            "--budget".into(),
+            self.budget.as_millis().to_string(),
+            "--warmup".into(),
+            self.warmup.as_millis().to_string(),
+            "--min-samples".into(),
+            self.min_samples.to_string(),
+            "--max-samples".into(),
+            self.max_samples.to_string(),
+        ]
+    }
+}
+
+pub fn run_part<I: Copy, R: ToAnswer>(func: impl Fn(I) -> R, input: I, puzzle: PuzzleId, part: u8) {
     let part_str = format!("Part {part}");
+    let is_json = is_json_output();
+
+    let (answer, stats) = run_timed(func, input, |result| {
+        let answer = result.to_answer();
+        if !is_json {
+            print_result(&answer, &part_str, "");
+        }
+        answer
+    });
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    if is_json {
+        let part_result = PartResult {
+            part,
+            answer: answer.clone(),
+            stats,
+        };
+        println!("{}", JsonValue::from(&part_result).stringify().unwrap());
+    } else {
+        print_result(&answer, &part_str, &format_stats(&stats));
+    }
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    if let Answer::Solved(answer) = answer {
+        submit_result(answer, puzzle, part);
+    }
+}
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+/// Print the explanation of a solution, for `--explain`. See [`crate::solution`].
+pub fn run_explain<E: Display>(func: impl Fn(&str) -> Result<String, E>, input: &str) {
+    match func(input) {
+        Ok(explanation) => print!("{explanation}"),
+        Err(e) => {
+            eprintln!("{ANSI_RED}Could not explain the solution: {e}{ANSI_RESET}");
+            process::exit(1);
+        }
     }
 }
 
+/// The explanation of solutions that do not provide one.
+pub fn no_explanation(_input: &str) -> Result<String, &'static str> {
+    Err("this solution has no explanation, see `solution!` for how to add one")
+}
+
+/// Run a part without printing anything and measure it with exact durations.
+/// Used by the `all-in-one` runner, see [`crate::template::registry`].
+pub fn measure_part<I: Copy, R: ToAnswer>(
+    func: impl Fn(I) -> R,
+    input: I,
+    part: u8,
+    config: Option<&BenchConfig>,
+) -> PartResult {
+    let timer = Instant::now();
+    let result = func(input);
+    let base_time = timer.elapsed();
+
+    let stats = match config {
+        Some(config) => bench(&func, input, base_time, config),
+        None => Stats::from_samples(&[base_time]),
+    };
+
+    PartResult {
+        part,
+        answer: result.to_answer(),
+        stats,
+    }
+}
+
+/// Whether the solution was invoked with `--json`, i.e. by `run_multi` rather than a human.
+fn is_json_output() -> bool {
+    env::args().any(|x| x == "--json")
+}
+
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
-fn run_timed<I: Copy, T>(
-    func: impl Fn(I) -> T,
-    input: I,
-    hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+///     See [`BenchConfig`] for the flags that tune this.
+///
+/// The first result is passed to `hook`, whose output is returned together with the measurements.
+fn run_timed<I: Copy, T, U>(func: impl Fn(I) -> T, input: I, hook: impl Fn(&T) -> U) -> (U, Stats) {
     let timer = Instant::now();
     let result = {
         #[cfg(feature = "dhat-heap")]
@@ -39,25 +225,45 @@ fn run_timed<I: Copy, T>(
     };
     let base_time = timer.elapsed();
 
-    hook(&result);
+    let result = hook(&result);
+
+    let stats = if std::env::args().any(|x| x == "--time") {
+        if !is_json_output() {
+            let mut stdout = stdout();
+            print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+            let _ = stdout.flush();
+        }
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+        bench(func, input, base_time, &BenchConfig::from_args())
     } else {
-        (base_time, 1)
+        Stats::from_samples(&[base_time])
     };
 
-    (result, run.0, run.1)
+    (result, stats)
 }
 
-fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
+fn bench<I: Copy, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: Duration,
+    config: &BenchConfig,
+) -> Stats {
+    // warm up caches and branch predictors. the initial run counts towards the warm-up.
+    let mut warmup_time = base_time;
+    let mut warmup_iterations = 1;
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+    while warmup_time < config.warmup {
+        let timer = Instant::now();
+        black_box(func(black_box(input)));
+        warmup_time += timer.elapsed();
+        warmup_iterations += 1;
+    }
 
-    let bench_iterations =
-        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+    let estimate = warmup_time.as_nanos() / warmup_iterations;
+    let bench_iterations = (config.budget.as_nanos() / cmp::max(estimate, 10)).clamp(
+        config.min_samples,
+        config.max_samples.max(config.min_samples),
+    );
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -67,72 +273,74 @@ fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
-}
-
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+    Stats::from_samples(&timers)
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
+pub(crate) fn format_stats(stats: &Stats) -> String {
+    let mean = stats.mean_duration();
+    if stats.samples <= 1 {
+        format!(" ({mean:.1?})")
     } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+        format!(
+            " ({mean:.1?} @ {} samples; {})",
+            stats.samples,
+            stats.summary()
+        )
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+pub(crate) fn print_result(result: &Answer, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
+    if !is_intermediate_result {
+        print!("\r");
+        println!("{}", format_result(result, part, duration_str));
+        return;
+    }
+
     match result {
-        Some(result) => {
-            if result.to_string().contains('\n') {
-                let str = format!("{part}: ▼ {duration_str}");
-                if is_intermediate_result {
-                    print!("{str}");
-                } else {
-                    print!("\r");
-                    println!("{str}");
-                    println!("{result}");
-                }
+        Answer::Solved(result) => {
+            if result.contains('\n') {
+                print!("{part}: ▼ ");
             } else {
-                let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}");
-                if is_intermediate_result {
-                    print!("{str}");
-                } else {
-                    print!("\r");
-                    println!("{str}");
-                }
+                print!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}");
             }
         }
-        None => {
-            if is_intermediate_result {
-                print!("{part}: ✖");
+        Answer::Unsolved => {
+            print!("{part}: ✖");
+        }
+        Answer::Failed(_) => {
+            print!("{part}: ⚠");
+        }
+    }
+}
+
+/// Format the final result of a part, possibly spanning multiple lines.
+pub(crate) fn format_result(result: &Answer, part: &str, duration_str: &str) -> String {
+    match result {
+        Answer::Solved(result) => {
+            if result.contains('\n') {
+                format!("{part}: ▼ {duration_str}\n{result}")
             } else {
-                print!("\r");
-                println!("{part}: ✖             ");
+                format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}")
             }
         }
+        Answer::Unsolved => format!("{part}: ✖             "),
+        Answer::Failed(error) => {
+            format!("{part}: ⚠ {ANSI_RED}{error}{ANSI_RESET}{duration_str}")
+        }
     }
 }
 
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
-    day: Day,
+///  2. a session cookie is configured.
+///  3. the answer is not already known to be wrong according to the ledger in `data/<year>/ledger.json`.
+fn submit_result(
+    answer: String,
+    puzzle: PuzzleId,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+) -> Option<Result<aoc_client::Verdict, aoc_client::AocCommandError>> {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
@@ -155,11 +363,188 @@ fn submit_result<T: Display>(
         return None;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+    if Source::from_args() != Source::Puzzle {
+        eprintln!("Not submitting `{answer}`: it was computed from a custom input.");
+        return None;
+    }
+
+    let ledger = match Ledger::read_from_file(puzzle.year) {
+        Ok(ledger) => ledger,
+        Err(e) => {
+            eprintln!("Not submitting `{answer}`: could not read the answer ledger {e}");
+            return None;
+        }
+    };
+    if let Err(rejection) = ledger.check(puzzle.day, part, &answer) {
+        eprintln!("Not submitting `{answer}`: {rejection}");
+        return None;
+    }
+
+    println!("Submitting result...");
+    let submission = match aoc_client::submit(puzzle, part, &answer) {
+        Ok(submission) => submission,
+        Err(e) => {
+            eprintln!("failed to submit result: {e}");
+            return Some(Err(e));
+        }
+    };
+
+    println!("{}", submission.message);
+
+    // re-read the ledger in case someone else submitted in the meantime.
+    match Ledger::read_from_file(puzzle.year) {
+        Ok(mut ledger) => {
+            ledger.record(puzzle.day, part, &answer, submission.verdict);
+            if let Err(e) = ledger.store_file(puzzle.year) {
+                eprintln!("failed to update answer ledger: {e}");
+            }
+        }
+        Err(e) => eprintln!("failed to update answer ledger, could not read {e}"),
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    if submission.verdict == aoc_client::Verdict::Correct {
+        if let Err(e) = answers::record(puzzle, part, &answer) {
+            eprintln!("failed to store answer: {e}");
+        }
+    }
+
+    Some(Ok(submission.verdict))
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&PartResult> for JsonValue {
+    fn from(value: &PartResult) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert(
+            "answer".into(),
+            match &value.answer {
+                Answer::Solved(x) => JsonValue::String(x.clone()),
+                _ => JsonValue::Null,
+            },
+        );
+        map.insert(
+            "error".into(),
+            match &value.answer {
+                Answer::Failed(x) => JsonValue::String(x.clone()),
+                _ => JsonValue::Null,
+            },
+        );
+        map.insert("nanos".into(), JsonValue::Number(value.stats.mean));
+        #[allow(clippy::cast_precision_loss)]
+        let samples = value.stats.samples as f64;
+        map.insert("samples".into(), JsonValue::Number(samples));
+        map.insert("success".into(), JsonValue::Boolean(value.is_success()));
+        map.insert("stats".into(), JsonValue::from(&value.stats));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl FromStr for PartResult {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let json = JsonValue::from_str(s).or(Err("not valid JSON."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected part result to be a JSON object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .map(|p| *p as u8)
+            .ok_or("Expected result.part to be a number.")?;
+
+        let answer = json
+            .get("answer")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected result.answer to be null or string.")?;
+
+        let error = match json.get("error") {
+            Some(v) if !v.is_null() => Some(
+                v.get::<String>()
+                    .ok_or("Expected result.error to be null or string.")?,
+            ),
+            _ => None,
+        };
+
+        let stats = json
+            .get("stats")
+            .ok_or("Expected result.stats to be present.")
+            .map(Stats::try_from)??;
+
+        let answer = match (answer, error) {
+            (Some(answer), _) => Answer::Solved(answer.clone()),
+            (None, Some(error)) => Answer::Failed(error.clone()),
+            (None, None) => Answer::Unsolved,
+        };
+
+        Ok(PartResult {
+            part,
+            answer,
+            stats,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Answer, PartResult, ToAnswer};
+    use crate::template::stats::Stats;
+    use std::time::Duration;
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn roundtrips_part_results() {
+        let result = PartResult {
+            part: 2,
+            answer: Answer::Solved("(1 @ 2 samples)".into()),
+            stats: Stats::from_samples(&[Duration::from_nanos(74130), Duration::from_nanos(100)]),
+        };
+        let json = JsonValue::from(&result).stringify().unwrap();
+        assert!(json.contains(r#""success":true"#));
+        assert_eq!(json.parse::<PartResult>().unwrap(), result);
+    }
+
+    #[test]
+    fn roundtrips_missing_answers() {
+        let result = PartResult {
+            part: 1,
+            answer: Answer::Unsolved,
+            stats: Stats::default(),
+        };
+        let json = JsonValue::from(&result).stringify().unwrap();
+        assert!(json.contains(r#""success":false"#));
+        assert_eq!(json.parse::<PartResult>().unwrap(), result);
+    }
+
+    #[test]
+    fn roundtrips_errors() {
+        let result = PartResult {
+            part: 1,
+            answer: Answer::Failed("invalid input".into()),
+            stats: Stats::default(),
+        };
+        let json = JsonValue::from(&result).stringify().unwrap();
+        assert!(json.contains(r#""success":false"#));
+        assert_eq!(json.parse::<PartResult>().unwrap(), result);
+    }
+
+    #[test]
+    fn converts_return_types() {
+        assert_eq!(Some(42).to_answer(), Answer::Solved("42".into()));
+        assert_eq!(None::<u32>.to_answer(), Answer::Unsolved);
+        assert_eq!(Ok::<_, String>(42).to_answer(), Answer::Solved("42".into()));
+        assert_eq!(
+            Err::<u32, _>("no guard").to_answer(),
+            Answer::Failed("no guard".into())
+        );
+    }
 }
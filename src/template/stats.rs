@@ -0,0 +1,207 @@
+/// Summary statistics over benchmark samples.
+use std::{collections::HashMap, time::Duration};
+use tinyjson::JsonValue;
+
+/// Statistics of a set of benchmark samples, in nanoseconds.
+/// Outliers (outside of 1.5 times the interquartile range) are rejected before computing them,
+/// except for `min` and `max`, which cover all samples.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Stats {
+    pub mean: f64,
+    pub median: f64,
+    /// Fastest sample, including outliers.
+    pub min: f64,
+    /// Slowest sample, including outliers.
+    pub max: f64,
+    pub stddev: f64,
+    pub p95: f64,
+    /// Number of samples taken, including outliers.
+    pub samples: u128,
+    /// Number of samples that were rejected as outliers.
+    pub outliers: u128,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        #[allow(clippy::cast_precision_loss)]
+        let mut nanos: Vec<f64> = samples.iter().map(|d| d.as_nanos() as f64).collect();
+        nanos.sort_unstable_by(f64::total_cmp);
+
+        let total = nanos.len();
+        let (min, max) = match (nanos.first(), nanos.last()) {
+            (Some(&min), Some(&max)) => (min, max),
+            _ => return Self::default(),
+        };
+
+        // Tukey's fences need a few samples to be meaningful.
+        if nanos.len() >= 4 {
+            let q1 = percentile(&nanos, 25.0);
+            let q3 = percentile(&nanos, 75.0);
+            let iqr = q3 - q1;
+            let (lower, upper) = (q1 - 1.5 * iqr, q3 + 1.5 * iqr);
+            nanos.retain(|x| *x >= lower && *x <= upper);
+        }
+
+        if nanos.is_empty() {
+            return Self::default();
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        let n = nanos.len() as f64;
+        let mean = nanos.iter().sum::<f64>() / n;
+        let variance = if nanos.len() > 1 {
+            nanos.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0)
+        } else {
+            0.0
+        };
+
+        Self {
+            mean,
+            median: percentile(&nanos, 50.0),
+            min,
+            max,
+            stddev: variance.sqrt(),
+            p95: percentile(&nanos, 95.0),
+            samples: total as u128,
+            outliers: (total - nanos.len()) as u128,
+        }
+    }
+
+    pub fn mean_duration(&self) -> Duration {
+        to_duration(self.mean)
+    }
+
+    pub fn median_duration(&self) -> Duration {
+        to_duration(self.median)
+    }
+
+    /// Format the spread of the samples for humans, e.g. `median 70.0ns, p95 90.0ns, ...`.
+    pub fn summary(&self) -> String {
+        let mut s = format!(
+            "median {:.1?}, p95 {:.1?}, min {:.1?}, max {:.1?}, σ {:.1?}",
+            self.median_duration(),
+            to_duration(self.p95),
+            to_duration(self.min),
+            to_duration(self.max),
+            to_duration(self.stddev)
+        );
+        if self.outliers > 0 {
+            s.push_str(&format!(", {} outliers", self.outliers));
+        }
+        s
+    }
+}
+
+/// A number of nanoseconds as a duration, rounded to whole nanoseconds.
+pub(crate) fn to_duration(nanos: f64) -> Duration {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    Duration::from_nanos(nanos.round() as u64)
+}
+
+/// Linearly interpolated percentile of sorted values.
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    #[allow(clippy::cast_precision_loss)]
+    let rank = p / 100.0 * (sorted.len() - 1) as f64;
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let (lo, hi) = (rank.floor() as usize, rank.ceil() as usize);
+    sorted[lo] + (sorted[hi] - sorted[lo]) * (rank - rank.floor())
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Stats> for JsonValue {
+    fn from(value: &Stats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("mean".into(), JsonValue::Number(value.mean));
+        map.insert("median".into(), JsonValue::Number(value.median));
+        map.insert("min".into(), JsonValue::Number(value.min));
+        map.insert("max".into(), JsonValue::Number(value.max));
+        map.insert("stddev".into(), JsonValue::Number(value.stddev));
+        map.insert("p95".into(), JsonValue::Number(value.p95));
+        #[allow(clippy::cast_precision_loss)]
+        let (samples, outliers) = (value.samples as f64, value.outliers as f64);
+        map.insert("samples".into(), JsonValue::Number(samples));
+        map.insert("outliers".into(), JsonValue::Number(outliers));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Stats {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected stats to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected stats.{key} to be a number."))
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(Stats {
+            mean: number("mean")?,
+            median: number("median")?,
+            min: number("min")?,
+            max: number("max")?,
+            stddev: number("stddev")?,
+            p95: number("p95")?,
+            samples: number("samples")? as u128,
+            outliers: number("outliers")? as u128,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Stats;
+    use std::time::Duration;
+
+    fn nanos(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|n| Duration::from_nanos(*n)).collect()
+    }
+
+    #[test]
+    fn computes_statistics() {
+        let stats = Stats::from_samples(&nanos(&[10, 20, 30, 40, 50]));
+        assert_eq!(stats.mean, 30.0);
+        assert_eq!(stats.median, 30.0);
+        assert_eq!(stats.min, 10.0);
+        assert_eq!(stats.max, 50.0);
+        assert_eq!(stats.p95, 48.0);
+        assert!((stats.stddev - 15.811_388).abs() < 1e-6);
+        assert_eq!(stats.samples, 5);
+        assert_eq!(stats.outliers, 0);
+    }
+
+    #[test]
+    fn rejects_outliers() {
+        let stats = Stats::from_samples(&nanos(&[10, 11, 10, 12, 11, 10, 1000]));
+        assert_eq!(stats.outliers, 1);
+        assert_eq!(stats.samples, 7);
+        assert_eq!(stats.median, 10.5);
+
+        // the outlier still shows in the range of all samples.
+        assert_eq!((stats.min, stats.max), (10.0, 1000.0));
+    }
+
+    #[test]
+    fn handles_single_sample() {
+        let stats = Stats::from_samples(&nanos(&[42]));
+        assert_eq!(stats.mean, 42.0);
+        assert_eq!(stats.median, 42.0);
+        assert_eq!(stats.stddev, 0.0);
+        assert_eq!(stats.samples, 1);
+    }
+
+    #[test]
+    fn handles_no_samples() {
+        assert_eq!(Stats::from_samples(&[]), Stats::default());
+    }
+}
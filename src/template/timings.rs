@@ -1,9 +1,14 @@
 use std::{collections::HashMap, fs, io::Error, str::FromStr};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::{
+    data_dir,
+    history::{Run, RunInfo},
+    stats::Stats,
+    Day, Year,
+};
 
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+static TIMINGS_FILE_NAME: &str = "timings.json";
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
@@ -11,33 +16,114 @@ pub struct Timing {
     pub day: Day,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
+    /// Sample statistics of part 1, absent for timings stored before they were recorded.
+    pub part_1_stats: Option<Stats>,
+    /// Sample statistics of part 2, absent for timings stored before they were recorded.
+    pub part_2_stats: Option<Stats>,
+    /// Error returned by part 1, if it failed.
+    pub part_1_error: Option<String>,
+    /// Error returned by part 2, if it failed.
+    pub part_2_error: Option<String>,
     pub total_nanos: f64,
 }
 
+/// The statistic of the samples that a part time is.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Measure {
+    Median,
+    /// Timings stored before sample statistics were recorded only have the mean.
+    Mean,
+}
+
+impl Timing {
+    fn part(&self, part: u8) -> Option<(Option<&str>, Option<&Stats>)> {
+        match part {
+            1 => Some((self.part_1.as_deref(), self.part_1_stats.as_ref())),
+            2 => Some((self.part_2.as_deref(), self.part_2_stats.as_ref())),
+            _ => None,
+        }
+    }
+
+    /// Representative time of a part in nanoseconds: the median if sample statistics are
+    /// present, otherwise the stored (mean) duration.
+    pub fn part_nanos(&self, part: u8) -> Option<f64> {
+        let (timing, stats) = self.part(part)?;
+        stats
+            .map(|s| s.median)
+            .or_else(|| timing.and_then(parse_duration_nanos))
+    }
+
+    /// Mean time of a part in nanoseconds.
+    fn part_mean_nanos(&self, part: u8) -> Option<f64> {
+        let (timing, stats) = self.part(part)?;
+        stats
+            .map(|s| s.mean)
+            .or_else(|| timing.and_then(parse_duration_nanos))
+    }
+
+    /// Times of a part in `baseline` and in this timing, in nanoseconds, so that they can be
+    /// compared: the medians if both have sample statistics, otherwise the means.
+    pub fn part_nanos_against(&self, baseline: &Timing, part: u8) -> Option<(f64, f64, Measure)> {
+        let (_, baseline_stats) = baseline.part(part)?;
+        let (_, stats) = self.part(part)?;
+
+        match (baseline_stats, stats) {
+            (Some(baseline), Some(current)) => {
+                Some((baseline.median, current.median, Measure::Median))
+            }
+            _ => Some((
+                baseline.part_mean_nanos(part)?,
+                self.part_mean_nanos(part)?,
+                Measure::Mean,
+            )),
+        }
+    }
+}
+
+/// Parse a duration formatted with `{:?}`, e.g. `74.1ns` or `1.2ms`, to nanoseconds.
+fn parse_duration_nanos(s: &str) -> Option<f64> {
+    // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+    let (value, factor) = if let Some(v) = s.strip_suffix("ns") {
+        (v, 1_f64)
+    } else if let Some(v) = s.strip_suffix("µs") {
+        (v, 1_000_f64)
+    } else if let Some(v) = s.strip_suffix("ms") {
+        (v, 1_000_000_f64)
+    } else {
+        (s.strip_suffix('s')?, 1_000_000_000_f64)
+    };
+
+    value.trim().parse::<f64>().ok().map(|x| x * factor)
+}
+
 /// Represents benchmark times for a set of days.
 /// Can be serialized from / to JSON.
 #[derive(Clone, Debug, Default)]
 pub struct Timings {
+    /// The latest timing of each day.
     pub data: Vec<Timing>,
+    /// Every stored run, oldest first. Only ever appended to.
+    pub history: Vec<Run>,
 }
 
 impl Timings {
-    /// Dehydrate timings to a JSON file.
-    pub fn store_file(&self) -> Result<(), Error> {
+    /// Dehydrate timings to `data/<year>/timings.json`.
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        let mut file = fs::File::create(data_dir(year).join(TIMINGS_FILE_NAME))?;
         json.format_to(&mut file)
     }
 
     /// Rehydrate timings from a JSON file. If not present, returns empty timings.
-    pub fn read_from_file() -> Self {
-        fs::read_to_string(TIMINGS_FILE_PATH)
+    pub fn read_from_file(year: Year) -> Self {
+        fs::read_to_string(data_dir(year).join(TIMINGS_FILE_NAME))
             .map_err(|x| x.to_string())
             .and_then(Timings::try_from)
             .unwrap_or_default()
     }
 
     /// Merge two sets of timings, overwriting `self` with `other` if present.
+    /// The history of `self` is kept, see [`Timings::append_history`] to extend it.
     pub fn merge(&self, new: &Self) -> Self {
         let mut data: Vec<Timing> = vec![];
 
@@ -51,8 +137,24 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
-        Timings { data }
+        data.sort_unstable_by_key(|a| a.day);
+        Timings {
+            data,
+            history: self.history.clone(),
+        }
+    }
+
+    /// Append every timing of `new` to the history, tagged with `info`.
+    pub fn append_history(&mut self, new: &Self, info: &RunInfo) {
+        self.history.extend(new.data.iter().map(|timing| Run {
+            timing: timing.clone(),
+            info: info.clone(),
+        }));
+    }
+
+    /// All stored runs of a day, oldest first.
+    pub fn history_of(&self, day: Day) -> impl Iterator<Item = &Run> {
+        self.history.iter().filter(move |run| run.timing.day == day)
     }
 
     /// Sum up total duration of timings as millis.
@@ -60,6 +162,10 @@ impl Timings {
         self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
     }
 
+    pub fn get(&self, day: Day) -> Option<&Timing> {
+        self.data.iter().find(|t| t.day == day)
+    }
+
     pub fn is_day_complete(&self, day: Day) -> bool {
         self.data
             .iter()
@@ -78,6 +184,11 @@ impl From<Timings> for JsonValue {
             JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
         );
 
+        map.insert(
+            "history".into(),
+            JsonValue::Array(value.history.iter().map(JsonValue::from).collect()),
+        );
+
         JsonValue::Object(map)
     }
 }
@@ -88,19 +199,34 @@ impl TryFrom<String> for Timings {
     fn try_from(value: String) -> Result<Self, Self::Error> {
         let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
 
-        let json_data = json
+        let json = json
             .get::<HashMap<String, JsonValue>>()
-            .ok_or("expected JSON document to be an object.")?
+            .ok_or("expected JSON document to be an object.")?;
+
+        let json_data = json
             .get("data")
             .ok_or("expected JSON document to have key `data`.")?
             .get::<Vec<JsonValue>>()
             .ok_or("expected `json.data` to be an array.")?;
 
+        // the history is optional to keep reading timings stored before it was recorded.
+        let json_history = match json.get("history") {
+            Some(history) => history
+                .get::<Vec<JsonValue>>()
+                .ok_or("expected `json.history` to be an array.")?
+                .as_slice(),
+            None => &[],
+        };
+
         Ok(Timings {
             data: json_data
                 .iter()
                 .map(Timing::try_from)
                 .collect::<Result<_, _>>()?,
+            history: json_history
+                .iter()
+                .map(Run::try_from)
+                .collect::<Result<_, _>>()?,
         })
     }
 }
@@ -133,6 +259,26 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        for (key, stats) in [
+            ("part_1_stats", &value.part_1_stats),
+            ("part_2_stats", &value.part_2_stats),
+        ] {
+            map.insert(
+                key.into(),
+                stats.as_ref().map_or(JsonValue::Null, JsonValue::from),
+            );
+        }
+
+        for (key, error) in [
+            ("part_1_error", &value.part_1_error),
+            ("part_2_error", &value.part_2_error),
+        ] {
+            map.insert(
+                key.into(),
+                error.clone().map_or(JsonValue::Null, JsonValue::String),
+            );
+        }
+
         JsonValue::Object(map)
     }
 }
@@ -166,10 +312,29 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // stats are optional to keep reading timings stored before they were recorded.
+        let stats = |key: &str| match json.get(key) {
+            Some(v) if !v.is_null() => Stats::try_from(v).map(Some),
+            _ => Ok(None),
+        };
+
+        // errors are optional to keep reading timings stored before they were recorded.
+        let error = |key: &str| match json.get(key) {
+            Some(v) if !v.is_null() => v
+                .get::<String>()
+                .map(|e| Some(e.clone()))
+                .ok_or(format!("Expected timing.{key} to be null or string.")),
+            _ => Ok(None),
+        };
+
         Ok(Timing {
             day,
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
+            part_1_stats: stats("part_1_stats")?,
+            part_2_stats: stats("part_2_stats")?,
+            part_1_error: error("part_1_error")?,
+            part_2_error: error("part_2_error")?,
             total_nanos,
         })
     }
@@ -190,21 +355,34 @@ mod tests {
                     day: day!(1),
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_error: None,
+                    part_2_error: None,
                     total_nanos: 3e+10,
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_error: None,
+                    part_2_error: None,
                     total_nanos: 7e+10,
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_error: None,
+                    part_2_error: None,
                     total_nanos: 4e+10,
                 },
             ],
+            history: vec![],
         }
     }
 
@@ -268,6 +446,62 @@ mod tests {
         }
     }
 
+    mod history {
+        use super::get_mock_timings;
+        use crate::{
+            day,
+            template::{history::RunInfo, timings::Timings},
+        };
+        use tinyjson::JsonValue;
+
+        fn get_mock_info(timestamp: u64) -> RunInfo {
+            RunInfo {
+                timestamp,
+                commit: Some("abc123".into()),
+                profile: "release".into(),
+                machine: "test (linux-x86_64)".into(),
+            }
+        }
+
+        #[test]
+        fn appends_runs_on_merge() {
+            let mut stored = Timings::default();
+            let new = get_mock_timings();
+            stored = stored.merge(&new);
+            stored.append_history(&new, &get_mock_info(1));
+            stored = stored.merge(&new);
+            stored.append_history(&new, &get_mock_info(2));
+
+            assert_eq!(stored.data.len(), 3);
+            assert_eq!(stored.history.len(), 6);
+
+            let runs: Vec<_> = stored.history_of(day!(1)).collect();
+            assert_eq!(runs.len(), 2);
+            assert_eq!(runs[0].info.timestamp, 1);
+            assert_eq!(runs[1].info.timestamp, 2);
+        }
+
+        #[test]
+        fn roundtrips_history() {
+            let mut timings = get_mock_timings();
+            timings.append_history(&get_mock_timings(), &get_mock_info(1_733_666_580));
+
+            let json = JsonValue::from(timings).stringify().unwrap();
+            let parsed = Timings::try_from(json).unwrap();
+
+            assert_eq!(parsed.history.len(), 3);
+            assert_eq!(parsed.history[0].info, get_mock_info(1_733_666_580));
+            assert_eq!(parsed.history[0].timing.part_1, Some("10ms".into()));
+        }
+
+        #[test]
+        fn reads_timings_without_history() {
+            let json = r#"{ "data": [] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.history.len(), 0);
+        }
+    }
+
     mod is_day_complete {
         use crate::{
             day,
@@ -281,11 +515,16 @@ mod tests {
                     day: day!(1),
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_error: None,
+                    part_2_error: None,
                     total_nanos: 3_000_000_000_f64,
                 }],
+                history: vec![],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -295,11 +534,16 @@ mod tests {
                     day: day!(1),
                     part_1: Some("1ms".into()),
                     part_2: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_error: None,
+                    part_2_error: None,
                     total_nanos: 1_000_000_000_f64,
                 }],
+                history: vec![],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -309,11 +553,100 @@ mod tests {
                     day: day!(1),
                     part_1: None,
                     part_2: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_error: None,
+                    part_2_error: None,
                     total_nanos: 0.0,
                 }],
+                history: vec![],
+            };
+
+            assert_eq!(timings.is_day_complete(day!(1)), false);
+        }
+    }
+
+    mod part_nanos {
+        use crate::{
+            day,
+            template::{
+                stats::Stats,
+                timings::{Measure, Timing},
+            },
+        };
+
+        #[test]
+        fn prefers_median_from_stats() {
+            let timing = Timing {
+                day: day!(1),
+                part_1: Some("10.0ms".into()),
+                part_2: Some("1.5µs".into()),
+                part_1_stats: Some(Stats {
+                    mean: 10_000_000.0,
+                    median: 9_000_000.0,
+                    ..Stats::default()
+                }),
+                part_2_stats: None,
+                part_1_error: None,
+                part_2_error: None,
+                total_nanos: 10_001_500.0,
+            };
+
+            assert_eq!(timing.part_nanos(1), Some(9_000_000.0));
+            assert_eq!(timing.part_nanos(2), Some(1_500.0));
+        }
+
+        #[test]
+        fn parses_stored_durations() {
+            let timing = Timing {
+                day: day!(1),
+                part_1: Some("74.13ns".into()),
+                part_2: Some("2.0s".into()),
+                part_1_stats: None,
+                part_2_stats: None,
+                part_1_error: None,
+                part_2_error: None,
+                total_nanos: 0.0,
+            };
+
+            assert_eq!(timing.part_nanos(1), Some(74.13));
+            assert_eq!(timing.part_nanos(2), Some(2_000_000_000.0));
+        }
+
+        #[test]
+        fn compares_like_with_like() {
+            let stats = |mean: f64, median: f64| Stats {
+                mean,
+                median,
+                ..Stats::default()
+            };
+            let timing = |part_1_stats: Option<Stats>| Timing {
+                day: day!(1),
+                part_1: Some("10.0ms".into()),
+                part_2: None,
+                part_1_stats,
+                part_2_stats: None,
+                part_1_error: None,
+                part_2_error: None,
+                total_nanos: 0.0,
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            let with_stats = timing(Some(stats(12_000_000.0, 8_000_000.0)));
+            let without_stats = timing(None);
+
+            assert_eq!(
+                with_stats.part_nanos_against(&timing(Some(stats(0.0, 9_000_000.0))), 1),
+                Some((9_000_000.0, 8_000_000.0, Measure::Median))
+            );
+            assert_eq!(
+                with_stats.part_nanos_against(&without_stats, 1),
+                Some((10_000_000.0, 12_000_000.0, Measure::Mean))
+            );
+            assert_eq!(
+                without_stats.part_nanos_against(&with_stats, 1),
+                Some((12_000_000.0, 10_000_000.0, Measure::Mean))
+            );
+            assert_eq!(with_stats.part_nanos_against(&without_stats, 2), None);
         }
     }
 
@@ -333,8 +666,13 @@ mod tests {
                     day: day!(3),
                     part_1: None,
                     part_2: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_error: None,
+                    part_2_error: None,
                     total_nanos: 0_f64,
                 }],
+                history: vec![],
             };
             let merged = timings.merge(&other);
             assert_eq!(merged.data.len(), 4);
@@ -353,8 +691,13 @@ mod tests {
                     day: day!(2),
                     part_1: None,
                     part_2: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_error: None,
+                    part_2_error: None,
                     total_nanos: 0_f64,
                 }],
+                history: vec![],
             };
             let merged = timings.merge(&other);
 
@@ -0,0 +1,192 @@
+//! Writes frames to an animated GIF or a sequence of PNG files.
+//!
+//! Images have no font, so every cell is drawn as a square: highlighted cells in their color,
+//! `.` and spaces as background, `#` in white and any other character in a muted color picked
+//! by the character. Captions are not drawn.
+
+use std::{
+    fs::{self, File},
+    io::{self, BufWriter},
+    path::{Path, PathBuf},
+};
+
+use super::{Color, Frame};
+
+/// Width and height of a cell in pixels.
+const CELL_SIZE: usize = 6;
+
+/// Time between two frames of a GIF, in hundredths of a second.
+const GIF_DELAY: u16 = 10;
+
+const BACKGROUND: usize = 0;
+const WALL: usize = 1;
+const MUTED: usize = 2;
+const MUTED_COLORS: usize = 10;
+const HIGHLIGHT: usize = MUTED + MUTED_COLORS;
+
+/// RGB colors of the cells, see the constants above for their order.
+#[rustfmt::skip]
+const PALETTE: [[u8; 3]; HIGHLIGHT + 6] = [
+    // background and walls
+    [0x0f, 0x0f, 0x23], [0xcc, 0xcc, 0xcc],
+    // muted colors for other characters
+    [0x4e, 0x5d, 0x94], [0x5b, 0x8a, 0x72], [0x8a, 0x6f, 0x4e], [0x7a, 0x4e, 0x7a],
+    [0x4e, 0x7a, 0x8a], [0x8a, 0x4e, 0x5b], [0x6b, 0x7a, 0x4e], [0x5d, 0x5d, 0x6e],
+    [0x94, 0x7a, 0x5d], [0x4e, 0x8a, 0x8a],
+    // highlights, in the order of `Color`
+    [0xff, 0x44, 0x44], [0x44, 0xdd, 0x44], [0xff, 0xff, 0x66], [0x66, 0x99, 0xff],
+    [0xff, 0x66, 0xff], [0x66, 0xff, 0xff],
+];
+
+enum Output {
+    /// The encoder is created with the size of the first frame.
+    Gif(Option<gif::Encoder<BufWriter<File>>>),
+    Png,
+}
+
+pub struct Writer {
+    path: PathBuf,
+    output: Output,
+    /// Size of the first frame in cells, later frames are cropped or padded to it.
+    size: Option<(usize, usize)>,
+    frames: usize,
+}
+
+impl Writer {
+    /// Write an animated GIF if `path` ends in `.gif`, otherwise PNG files into the directory
+    /// `path`.
+    pub fn new(path: &Path) -> io::Result<Self> {
+        let output = if path.extension().is_some_and(|ext| ext == "gif") {
+            Output::Gif(None)
+        } else {
+            fs::create_dir_all(path)?;
+            Output::Png
+        };
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            output,
+            size: None,
+            frames: 0,
+        })
+    }
+
+    pub fn write(&mut self, frame: &Frame) -> io::Result<()> {
+        let (rows, cols) = *self
+            .size
+            .get_or_insert((frame.cells.rows(), frame.cells.cols()));
+        let (width, height) = (cols * CELL_SIZE, rows * CELL_SIZE);
+        let pixels = pixels(frame, rows, cols);
+        self.frames += 1;
+
+        match &mut self.output {
+            Output::Gif(encoder) => {
+                let (width, height) = (gif_size(width)?, gif_size(height)?);
+                let encoder = match encoder {
+                    Some(encoder) => encoder,
+                    None => {
+                        let file = BufWriter::new(File::create(&self.path)?);
+                        let mut new =
+                            gif::Encoder::new(file, width, height, PALETTE.as_flattened())
+                                .map_err(io::Error::other)?;
+                        new.set_repeat(gif::Repeat::Infinite)
+                            .map_err(io::Error::other)?;
+                        encoder.insert(new)
+                    }
+                };
+
+                let mut image = gif::Frame::from_indexed_pixels(width, height, pixels, None);
+                image.delay = GIF_DELAY;
+                encoder.write_frame(&image).map_err(io::Error::other)
+            }
+            Output::Png => {
+                let path = self.path.join(format!("frame-{:05}.png", self.frames));
+                let file = BufWriter::new(File::create(path)?);
+
+                let mut encoder = png::Encoder::new(file, width as u32, height as u32);
+                encoder.set_color(png::ColorType::Indexed);
+                encoder.set_depth(png::BitDepth::Eight);
+                encoder.set_palette(PALETTE.as_flattened());
+                let mut writer = encoder.write_header().map_err(io::Error::other)?;
+                writer.write_image_data(&pixels).map_err(io::Error::other)
+            }
+        }
+    }
+
+    /// Complete the output, e.g. write the end of a GIF.
+    pub fn finish(self) {
+        let what = match self.output {
+            Output::Gif(_) => "",
+            Output::Png => " as PNG files",
+        };
+        // Dropping the encoder writes the GIF trailer.
+        drop(self.output);
+
+        eprintln!(
+            "Wrote {} frame(s){what} to \"{}\".",
+            self.frames,
+            self.path.display()
+        );
+    }
+}
+
+fn gif_size(pixels: usize) -> io::Result<u16> {
+    u16::try_from(pixels).map_err(|_| io::Error::other("frame is too large for a GIF"))
+}
+
+/// Palette index of a cell.
+fn palette_index(c: char, color: Option<Color>) -> u8 {
+    let index = match (c, color) {
+        (_, Some(color)) => HIGHLIGHT + color as usize,
+        ('.' | ' ', None) => BACKGROUND,
+        ('#', None) => WALL,
+        (c, None) => MUTED + c as usize % MUTED_COLORS,
+    };
+    index as u8
+}
+
+/// Palette indices of all pixels of `rows` times `cols` cells, row by row. Cells outside of
+/// the frame are background.
+fn pixels(frame: &Frame, rows: usize, cols: usize) -> Vec<u8> {
+    let width = cols * CELL_SIZE;
+    let mut pixels = vec![BACKGROUND as u8; width * rows * CELL_SIZE];
+
+    for ((row, col), &(c, color)) in frame.cells.iter() {
+        if row >= rows || col >= cols {
+            continue;
+        }
+
+        let index = palette_index(c, color);
+        for y in row * CELL_SIZE..(row + 1) * CELL_SIZE {
+            let start = y * width + col * CELL_SIZE;
+            pixels[start..start + CELL_SIZE].fill(index);
+        }
+    }
+
+    pixels
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Grid;
+
+    #[test]
+    fn draws_cells() {
+        let grid = Grid::parse("#.\n.7", |c| c).unwrap();
+        let frame = Frame::new(&grid, |&c| c).highlight((1, 0), Color::Red);
+
+        // Crop to the first row.
+        let cropped = pixels(&frame, 1, 2);
+        assert_eq!(cropped.len(), 2 * CELL_SIZE * CELL_SIZE);
+        assert_eq!(cropped[0], WALL as u8);
+        assert_eq!(cropped[CELL_SIZE], BACKGROUND as u8);
+
+        let full = pixels(&frame, 2, 2);
+        let second_row = 2 * CELL_SIZE * CELL_SIZE;
+        assert_eq!(full[second_row], (HIGHLIGHT + Color::Red as usize) as u8);
+        assert_eq!(full[second_row + CELL_SIZE], palette_index('7', None));
+    }
+}
@@ -0,0 +1,309 @@
+//! Step-by-step visualization of a solution's state, in the terminal or as images.
+//!
+//! Solutions pass a closure building a [`Frame`] to [`show`]. Frames are only built when the
+//! `visualize` feature is enabled and the solution runs with `--visualize` (terminal) or
+//! `--visualize-to <path>` (images), e.g. `cargo solve 6 --example --visualize`. Without the
+//! feature, [`show`] does nothing and the closure is never compiled into the binary.
+//!
+//! ```
+//! use advent_of_code::{grid::Grid, visualize::{self, Color, Frame}};
+//!
+//! let grid = Grid::parse("..#\n...", |c| c == '#').unwrap();
+//! visualize::show(|| {
+//!     Frame::new(&grid, |&wall| if wall { '#' } else { '.' })
+//!         .mark((1, 0), '@', Color::Red)
+//!         .caption("start")
+//! });
+//! ```
+//!
+//! While a frame is shown, `space` toggles playback, `enter` steps to the next frame, `+` and `-`
+//! change the playback speed, and `q` stops the visualization while the solution keeps running.
+
+use std::{fmt::Display, path::PathBuf};
+
+use crate::grid::{Grid, Pos};
+
+#[cfg(feature = "visualize")]
+mod image;
+#[cfg(feature = "visualize")]
+mod terminal;
+
+/// Where frames are shown.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Output {
+    /// `--visualize`: draw frames in the terminal.
+    Terminal,
+    /// `--visualize-to <path>`: write an animated GIF if the path ends in `.gif`, otherwise a
+    /// directory of PNG files.
+    Images(PathBuf),
+}
+
+impl Output {
+    /// The output selected by command-line arguments, if any.
+    pub fn from_args(args: &[String]) -> Option<Self> {
+        if let Some(index) = args.iter().position(|x| x == "--visualize-to") {
+            return args.get(index + 1).map(|path| Output::Images(path.into()));
+        }
+        args.iter()
+            .any(|x| x == "--visualize")
+            .then_some(Output::Terminal)
+    }
+
+    /// Arguments that select this output when passed to a solution binary.
+    pub fn to_args(&self) -> Vec<String> {
+        match self {
+            Output::Terminal => vec!["--visualize".into()],
+            Output::Images(path) => vec!["--visualize-to".into(), path.display().to_string()],
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Color {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+}
+
+impl Color {
+    fn ansi(self) -> &'static str {
+        match self {
+            Color::Red => "\x1b[1;31m",
+            Color::Green => "\x1b[1;32m",
+            Color::Yellow => "\x1b[1;33m",
+            Color::Blue => "\x1b[1;34m",
+            Color::Magenta => "\x1b[1;35m",
+            Color::Cyan => "\x1b[1;36m",
+        }
+    }
+}
+
+/// A snapshot of a grid, with optionally highlighted cells and a caption.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Frame {
+    cells: Grid<(char, Option<Color>)>,
+    caption: String,
+}
+
+impl Frame {
+    /// Draw every cell of `grid` as the character returned by `f`.
+    pub fn new<T>(grid: &Grid<T>, mut f: impl FnMut(&T) -> char) -> Self {
+        Self {
+            cells: grid.map(|cell| (f(cell), None)),
+            caption: String::new(),
+        }
+    }
+
+    /// Lay out a sequence of characters in rows of `width`, e.g. a long one-line puzzle input.
+    /// The character at index `i` ends up at position `(i / width, i % width)`.
+    pub fn wrap(chars: impl IntoIterator<Item = char>, width: usize) -> Self {
+        let width = width.max(1);
+        let mut cells: Vec<_> = chars.into_iter().map(|c| (c, None)).collect();
+        let rows = cells.len().div_ceil(width);
+        cells.resize(rows * width, (' ', None));
+
+        Self {
+            cells: Grid::from_vec(rows, width, cells).expect("cells fill all rows"),
+            caption: String::new(),
+        }
+    }
+
+    /// Color the cell at `pos`. Positions outside of the frame are ignored.
+    pub fn highlight(mut self, pos: Pos, color: Color) -> Self {
+        if let Some(cell) = self.cells.get_mut(pos) {
+            cell.1 = Some(color);
+        }
+        self
+    }
+
+    /// Color all cells at `positions`, see [`Frame::highlight`].
+    pub fn highlight_all(self, positions: impl IntoIterator<Item = Pos>, color: Color) -> Self {
+        positions
+            .into_iter()
+            .fold(self, |frame, pos| frame.highlight(pos, color))
+    }
+
+    /// Replace the cell at `pos` with a colored character, e.g. to draw a moving object.
+    pub fn mark(mut self, pos: Pos, c: char, color: Color) -> Self {
+        if let Some(cell) = self.cells.get_mut(pos) {
+            *cell = (c, Some(color));
+        }
+        self
+    }
+
+    /// A line of text shown above the grid.
+    pub fn caption(mut self, caption: impl Into<String>) -> Self {
+        self.caption = caption.into();
+        self
+    }
+
+    /// Render at most `rows` times `cols` cells, with ANSI colors.
+    fn render(&self, rows: usize, cols: usize) -> String {
+        let mut out = String::new();
+        if !self.caption.is_empty() {
+            out.push_str(&self.caption);
+            out.push('\n');
+        }
+
+        for row in self.cells.iter_rows().take(rows) {
+            for &(c, color) in row.iter().take(cols) {
+                match color {
+                    Some(color) => out.push_str(&format!("{}{c}\x1b[0m", color.ansi())),
+                    None => out.push(c),
+                }
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+/// Renders the caption and all cells, with ANSI colors.
+impl Display for Frame {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.render(self.cells.rows(), self.cells.cols()))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "visualize")]
+enum Sink {
+    Off,
+    Terminal(terminal::Player),
+    Images(image::Writer),
+}
+
+#[cfg(feature = "visualize")]
+static SINK: std::sync::Mutex<Option<Sink>> = std::sync::Mutex::new(None);
+
+#[cfg(feature = "visualize")]
+fn with_sink<R>(f: impl FnOnce(&mut Sink) -> R) -> R {
+    let mut sink = SINK.lock().unwrap();
+    let sink = sink.get_or_insert_with(|| {
+        let args: Vec<String> = std::env::args().collect();
+        let result = match Output::from_args(&args) {
+            None => return Sink::Off,
+            Some(Output::Terminal) => Ok(Sink::Terminal(terminal::Player::new())),
+            Some(Output::Images(path)) => image::Writer::new(&path).map(Sink::Images),
+        };
+        result.unwrap_or_else(|e| {
+            eprintln!("Could not start visualization: {e}");
+            Sink::Off
+        })
+    });
+    f(sink)
+}
+
+/// Show the frame built by `frame`, if the solution runs with `--visualize` or
+/// `--visualize-to`. In the terminal, blocks until the user steps to the next frame, or the
+/// playback delay has passed.
+#[cfg(feature = "visualize")]
+pub fn show(frame: impl FnOnce() -> Frame) {
+    if !is_active() {
+        return;
+    }
+
+    // Build the frame before taking the sink, as `frame` may use it too, e.g. via `is_active`.
+    let frame = frame();
+    with_sink(|sink| {
+        let result = match sink {
+            Sink::Off => return,
+            Sink::Terminal(player) => player.draw(&frame),
+            Sink::Images(writer) => writer.write(&frame).map(|_| true),
+        };
+
+        match result {
+            Ok(true) => {}
+            Ok(false) => finish_sink(sink),
+            Err(e) => {
+                eprintln!("Stopping visualization: {e}");
+                finish_sink(sink);
+            }
+        }
+    });
+}
+
+/// Does nothing, as the `visualize` feature is disabled.
+#[cfg(not(feature = "visualize"))]
+#[inline(always)]
+pub fn show(_frame: impl FnOnce() -> Frame) {}
+
+/// Whether frames passed to [`show`] are drawn, i.e. the solution runs with `--visualize` or
+/// `--visualize-to` and the visualization has not been stopped.
+pub fn is_active() -> bool {
+    #[cfg(feature = "visualize")]
+    return with_sink(|sink| !matches!(sink, Sink::Off));
+
+    #[cfg(not(feature = "visualize"))]
+    false
+}
+
+/// Stop the visualization and complete written images. Called at the end of a solution's
+/// `main`.
+pub fn finish() {
+    #[cfg(feature = "visualize")]
+    with_sink(finish_sink);
+}
+
+#[cfg(feature = "visualize")]
+fn finish_sink(sink: &mut Sink) {
+    if let Sink::Images(writer) = std::mem::replace(sink, Sink::Off) {
+        writer.finish();
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_highlights() {
+        let grid = Grid::parse("ab\ncd", |c| c).unwrap();
+        let frame = Frame::new(&grid, |&c| c)
+            .highlight((0, 1), Color::Green)
+            .mark((1, 0), '@', Color::Red)
+            .caption("step 1");
+        assert_eq!(
+            frame.to_string(),
+            "step 1\na\x1b[1;32mb\x1b[0m\n\x1b[1;31m@\x1b[0md\n"
+        );
+    }
+
+    #[test]
+    fn parses_output_args() {
+        let args = |args: &[&str]| -> Vec<String> { args.iter().map(|x| x.to_string()).collect() };
+
+        assert_eq!(Output::from_args(&args(&["2024-06"])), None);
+        for output in [Output::Terminal, Output::Images("walk.gif".into())] {
+            let mut all = args(&["2024-06", "--example"]);
+            all.extend(output.to_args());
+            assert_eq!(Output::from_args(&all), Some(output));
+        }
+    }
+
+    #[cfg(feature = "visualize")]
+    #[test]
+    fn builds_frames_outside_the_sink() {
+        let dir = std::env::temp_dir().join(format!("aoc-frames-{}", std::process::id()));
+        *SINK.lock().unwrap() = Some(Sink::Images(image::Writer::new(&dir).unwrap()));
+
+        // Would deadlock if `show` held the sink while building the frame.
+        show(|| Frame::wrap("ab".chars(), 2).caption(format!("active: {}", is_active())));
+        finish();
+
+        assert!(dir.join("frame-00001.png").exists());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn wraps_sequences() {
+        let frame = Frame::wrap("00...111".chars(), 3).highlight((2, 1), Color::Blue);
+        assert_eq!(frame.to_string(), "00.\n..1\n1\x1b[1;34m1\x1b[0m \n");
+    }
+}
@@ -0,0 +1,127 @@
+//! Draws frames in the terminal, with keyboard controls for the playback.
+
+use std::{
+    io::{self, IsTerminal, Write},
+    time::Duration,
+};
+
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    terminal,
+};
+
+use super::Frame;
+
+const MIN_DELAY: Duration = Duration::from_millis(5);
+const MAX_DELAY: Duration = Duration::from_secs(2);
+
+pub struct Player {
+    /// Whether the user can control the playback, otherwise frames are printed one after the
+    /// other.
+    interactive: bool,
+    playing: bool,
+    delay: Duration,
+    frames: usize,
+}
+
+impl Player {
+    pub fn new() -> Self {
+        Self {
+            interactive: io::stdin().is_terminal() && io::stderr().is_terminal(),
+            playing: false,
+            delay: Duration::from_millis(100),
+            frames: 0,
+        }
+    }
+
+    /// Draw a frame and wait for the next one. Returns `false` if the user stopped the
+    /// visualization.
+    pub fn draw(&mut self, frame: &Frame) -> io::Result<bool> {
+        self.frames += 1;
+        let mut out = io::stderr().lock();
+
+        if !self.interactive {
+            writeln!(out, "{}", frame.render(usize::MAX, usize::MAX))?;
+            return Ok(true);
+        }
+
+        // Leave room for the caption and the status line. Some terminals report no size.
+        let (rows, cols) = match terminal::size()? {
+            (0, _) | (_, 0) => (usize::MAX, usize::MAX),
+            (cols, rows) => ((rows as usize).saturating_sub(2), cols as usize),
+        };
+
+        crossterm::execute!(
+            out,
+            terminal::Clear(terminal::ClearType::All),
+            cursor::MoveTo(0, 0)
+        )?;
+        write!(out, "{}", frame.render(rows, cols))?;
+
+        let state = if self.playing {
+            format!("playing ({}ms)", self.delay.as_millis())
+        } else {
+            "paused".into()
+        };
+        writeln!(
+            out,
+            "frame {} · {state} · [space] play/pause  [enter] step  [+/-] speed  [q] quit",
+            self.frames
+        )?;
+        out.flush()?;
+
+        self.wait()
+    }
+
+    /// Handle keys until it is time for the next frame.
+    fn wait(&mut self) -> io::Result<bool> {
+        loop {
+            let timeout = self.playing.then_some(self.delay);
+            let Some(key) = read_key(timeout)? else {
+                // Playing, and the delay has passed.
+                return Ok(true);
+            };
+
+            match key.code {
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    std::process::exit(130);
+                }
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(false),
+                KeyCode::Char(' ') => {
+                    self.playing = !self.playing;
+                    if self.playing {
+                        return Ok(true);
+                    }
+                }
+                KeyCode::Enter | KeyCode::Right | KeyCode::Char('n') => {
+                    self.playing = false;
+                    return Ok(true);
+                }
+                KeyCode::Char('+') => self.delay = (self.delay / 2).max(MIN_DELAY),
+                KeyCode::Char('-') => self.delay = (self.delay * 2).min(MAX_DELAY),
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Wait for a key press, at most `timeout` if given. Raw mode is only enabled while
+/// waiting, so the terminal is usable again if the solution panics.
+fn read_key(timeout: Option<Duration>) -> io::Result<Option<KeyEvent>> {
+    terminal::enable_raw_mode()?;
+    let key = (|| loop {
+        if let Some(timeout) = timeout {
+            if !event::poll(timeout)? {
+                return Ok(None);
+            }
+        }
+        if let Event::Key(key) = event::read()? {
+            if key.kind == KeyEventKind::Press {
+                return Ok(Some(key));
+            }
+        }
+    })();
+    terminal::disable_raw_mode()?;
+    key
+}